@@ -0,0 +1,73 @@
+//! Counting how often the output buffer of `html!` grows, enabled with the
+//! `alloc-counting` feature.
+
+use alloc::string::String;
+use core::ops::{Deref, DerefMut};
+
+/// A `String` that counts how many times its capacity changes.
+///
+/// Literal text is written with [`push_str`](CountingString::push_str),
+/// which checks the capacity straight away. Splices write to the `String`
+/// underneath, so growth caused by a splice is only seen at the next check;
+/// if a single splice makes the buffer grow more than once, it's counted
+/// once.
+pub struct CountingString {
+    buffer: String,
+    size_hint: usize,
+    capacity: usize,
+    reallocations: usize,
+}
+
+impl CountingString {
+    pub fn with_capacity(size_hint: usize) -> CountingString {
+        let buffer = String::with_capacity(size_hint);
+        CountingString {
+            capacity: buffer.capacity(),
+            buffer,
+            size_hint,
+            reallocations: 0,
+        }
+    }
+
+    pub fn push_str(&mut self, string: &str) {
+        self.buffer.push_str(string);
+        self.check();
+    }
+
+    fn check(&mut self) {
+        if self.buffer.capacity() != self.capacity {
+            self.capacity = self.buffer.capacity();
+            self.reallocations += 1;
+        }
+    }
+
+    /// Logs how many times the buffer grew, and returns its contents.
+    ///
+    /// `location` is the `file:line` of the `html!` call.
+    pub fn finish(mut self, location: &'static str) -> String {
+        self.check();
+        tracing::debug!(
+            reallocations = self.reallocations,
+            size_hint = self.size_hint,
+            len = self.buffer.len(),
+            location,
+            "template reallocated {} times",
+            self.reallocations,
+        );
+        self.buffer
+    }
+}
+
+impl Deref for CountingString {
+    type Target = String;
+
+    fn deref(&self) -> &String {
+        &self.buffer
+    }
+}
+
+impl DerefMut for CountingString {
+    fn deref_mut(&mut self) -> &mut String {
+        &mut self.buffer
+    }
+}
@@ -0,0 +1,408 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::{Display, Write};
+
+use crate::Render;
+
+/// Builds a class list from some base classes, plus others that are only
+/// added when their condition is `true`.
+///
+/// Classes are separated by single spaces, with no leading or trailing
+/// whitespace. A class that appears more than once is kept only the first
+/// time.
+///
+/// The result is a plain [`String`], so it's escaped when spliced.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let base = "  btn btn-primary ";
+/// let is_active = true;
+/// let is_disabled = false;
+///
+/// let markup = html! {
+///     button class=(maud::cx(base, [("active", is_active), ("disabled", is_disabled)])) {
+///         "Click me"
+///     }
+/// };
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<button class="btn btn-primary active">Click me</button>"#,
+/// );
+/// ```
+pub fn cx<'a>(base: &'a str, conditional: impl IntoIterator<Item = (&'a str, bool)>) -> String {
+    let conditional = conditional
+        .into_iter()
+        .filter(|&(_, enabled)| enabled)
+        .map(|(classes, _)| classes);
+    let mut seen = Vec::new();
+    let mut output = String::new();
+    for class in core::iter::once(base)
+        .chain(conditional)
+        .flat_map(str::split_whitespace)
+    {
+        if seen.contains(&class) {
+            continue;
+        }
+        seen.push(class);
+        if !output.is_empty() {
+            output.push(' ');
+        }
+        output.push_str(class);
+    }
+    output
+}
+
+/// Builds a class list from a base class, plus a modifier class made from
+/// `value`, such as `status status-active`.
+///
+/// The modifier is the base class, a `-`, and the [`Display`] output of
+/// `value` in kebab case. To make the kebab case, the output is split into
+/// words, which are lowercased and joined with `-`. A new word starts:
+///
+/// * after any character that isn't a letter or digit, which is dropped;
+/// * at an uppercase letter that follows a lowercase letter or a digit, as
+///   in `InProgress`;
+/// * at the last uppercase letter of a run, if a lowercase letter follows
+///   it, as in `HTTPError`.
+///
+/// If the kebab case is empty, only the base class is returned.
+///
+/// The result is a plain [`String`], so it's escaped when spliced.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+/// use std::fmt;
+///
+/// enum Status {
+///     Active,
+///     OnHold,
+/// }
+///
+/// impl fmt::Display for Status {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         f.write_str(match self {
+///             Status::Active => "Active",
+///             Status::OnHold => "OnHold",
+///         })
+///     }
+/// }
+///
+/// let markup = html! {
+///     div class=(maud::enum_class("status", &Status::OnHold)) {}
+/// };
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<div class="status status-on-hold"></div>"#,
+/// );
+/// ```
+pub fn enum_class(base: &str, value: impl Display) -> String {
+    let base = base.trim();
+    let mut display = String::new();
+    write!(display, "{}", value).unwrap();
+    let modifier = kebab_case(&display);
+    let mut output = String::from(base);
+    if !modifier.is_empty() {
+        if !base.is_empty() {
+            output.push(' ');
+            output.push_str(base);
+            output.push('-');
+        }
+        output.push_str(&modifier);
+    }
+    output
+}
+
+/// Converts `text` to kebab case, as described for [`enum_class`].
+fn kebab_case(text: &str) -> String {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut output = String::with_capacity(text.len());
+    let mut in_word = false;
+    for (i, &c) in chars.iter().enumerate() {
+        if !c.is_alphanumeric() {
+            in_word = false;
+            continue;
+        }
+        let starts_word = !in_word
+            || c.is_uppercase() && {
+                let prev = chars[i - 1];
+                let next_is_lowercase = chars.get(i + 1).is_some_and(|next| next.is_lowercase());
+                prev.is_lowercase() || prev.is_numeric() || prev.is_uppercase() && next_is_lowercase
+            };
+        if starts_word && !output.is_empty() {
+            output.push('-');
+        }
+        output.extend(c.to_lowercase());
+        in_word = true;
+    }
+    output
+}
+
+/// Builds the value of a `srcset` attribute, from `(url, descriptor)` pairs
+/// such as `("hero@2x.png", "2x")` or `("hero-800.png", "800w")`.
+///
+/// Candidates are separated by `", "`. Whitespace in a URL is written as
+/// `%20`, and a comma at the start or end of a URL as `%2C`, as these would
+/// otherwise be read as separators. An empty descriptor is left out.
+///
+/// The result is a plain [`String`], so it's escaped when spliced. If
+/// there are no candidates, it's empty; use `srcset=[...]` with a filter to
+/// leave the attribute out entirely, as in the example below.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let variants = [("/pony.png", "1x"), ("/pony@2x.png", "2x")];
+/// let markup = html! {
+///     img srcset=(maud::srcset(&variants)) src="/pony.png";
+/// };
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<img srcset="/pony.png 1x, /pony@2x.png 2x" src="/pony.png">"#,
+/// );
+///
+/// let none: [(&str, &str); 0] = [];
+/// let srcset = Some(maud::srcset(&none)).filter(|srcset| !srcset.is_empty());
+/// let markup = html! { img srcset=[srcset] src="/pony.png"; };
+/// assert_eq!(markup.into_string(), r#"<img src="/pony.png">"#);
+/// ```
+pub fn srcset<U: AsRef<str>, D: AsRef<str>>(candidates: &[(U, D)]) -> String {
+    let mut output = String::new();
+    for (url, descriptor) in candidates {
+        if !output.is_empty() {
+            output.push_str(", ");
+        }
+        let url = url.as_ref();
+        for (i, c) in url.char_indices() {
+            match c {
+                c if c.is_ascii_whitespace() => output.push_str("%20"),
+                ',' if i == 0 || i == url.len() - 1 => output.push_str("%2C"),
+                c => output.push(c),
+            }
+        }
+        let descriptor = descriptor.as_ref().trim();
+        if !descriptor.is_empty() {
+            output.push(' ');
+            output.push_str(descriptor);
+        }
+    }
+    output
+}
+
+/// A set of attributes built at runtime, for when the attributes aren't
+/// known until then.
+///
+/// Splice it in attribute position, as with a raw `PreEscaped` string of
+/// attributes. Values are escaped, so unlike a raw string it's safe to fill
+/// an `Attrs` from untrusted input.
+///
+/// Each method sets or removes one attribute. If the same attribute is set
+/// twice, the later value wins, but it keeps its place from the first time.
+/// Attributes are written in that order, after the element's other
+/// attributes.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Attrs};
+///
+/// let is_active = true;
+/// let url: Option<&str> = None;
+/// let attrs = Attrs::new()
+///     .set("class", "card")
+///     .toggle("data-active", is_active)
+///     .optional("href", url);
+///
+/// assert_eq!(
+///     html! { a id="first" (attrs) { "Hi!" } }.into_string(),
+///     r#"<a id="first" class="card" data-active>Hi!</a>"#,
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Attrs {
+    /// The names and rendered values, with `None` for an empty attribute.
+    attrs: Vec<(String, Option<String>)>,
+}
+
+impl Attrs {
+    /// Creates an empty set of attributes.
+    pub fn new() -> Attrs {
+        Attrs::default()
+    }
+
+    /// Sets the attribute `name` to `value`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `name` isn't a valid attribute name, such as one that's
+    /// empty or contains whitespace, quotes, `=`, `/` or `>`. This is the
+    /// same for all the methods.
+    pub fn set(self, name: &str, value: impl Render) -> Attrs {
+        let mut buffer = String::new();
+        value.render_to(&mut buffer);
+        self.insert(name, Some(buffer))
+    }
+
+    /// Sets `name` as an empty attribute, such as `disabled`, if `on` is
+    /// `true`, and removes it otherwise.
+    pub fn toggle(self, name: &str, on: bool) -> Attrs {
+        if on {
+            self.insert(name, None)
+        } else {
+            self.remove(name)
+        }
+    }
+
+    /// Sets the attribute `name` to `value` if it's `Some`, and removes it
+    /// otherwise.
+    pub fn optional<T: Render>(self, name: &str, value: Option<T>) -> Attrs {
+        match value {
+            Some(value) => self.set(name, value),
+            None => self.remove(name),
+        }
+    }
+
+    /// Whether there are no attributes.
+    pub fn is_empty(&self) -> bool {
+        self.attrs.is_empty()
+    }
+
+    fn insert(mut self, name: &str, value: Option<String>) -> Attrs {
+        check_attr_name(name);
+        match self.attrs.iter_mut().find(|(other, _)| other == name) {
+            Some((_, old)) => *old = value,
+            None => self.attrs.push((name.into(), value)),
+        }
+        self
+    }
+
+    fn remove(mut self, name: &str) -> Attrs {
+        check_attr_name(name);
+        self.attrs.retain(|(other, _)| other != name);
+        self
+    }
+
+    /// Writes the attributes, separated by spaces.
+    pub(crate) fn push_to(&self, buffer: &mut String) {
+        for (i, (name, value)) in self.attrs.iter().enumerate() {
+            if i > 0 {
+                buffer.push(' ');
+            }
+            buffer.push_str(name);
+            if let Some(value) = value {
+                buffer.push_str("=\"");
+                buffer.push_str(value);
+                buffer.push('"');
+            }
+        }
+    }
+}
+
+fn check_attr_name(name: &str) {
+    let is_valid = !name.is_empty()
+        && !name.chars().any(|c| {
+            c.is_whitespace() || c.is_control() || matches!(c, '"' | '\'' | '>' | '/' | '=' | '<')
+        });
+    assert!(is_valid, "invalid attribute name {:?}", name);
+}
+
+/// Starts building a set of [htmx](https://htmx.org) `hx-*` attributes.
+///
+/// Each attribute has its own method, so a misspelled name is a compile
+/// error. Splice the result in attribute position, as with [`Attrs`]. The
+/// values are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let markup = html! {
+///     button (maud::hx().get("/ponies?page=2").target("#list").swap("beforeend")) {
+///         "More"
+///     }
+/// };
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         r##"<button hx-get="/ponies?page=2" hx-target="#list" hx-swap="beforeend">"##,
+///         "More</button>",
+///     ),
+/// );
+/// ```
+pub fn hx() -> Hx {
+    Hx::default()
+}
+
+/// A set of htmx attributes, built with [`hx`].
+///
+/// As with [`Attrs`], setting an attribute again replaces its value.
+#[derive(Clone, Debug, Default)]
+pub struct Hx {
+    pub(crate) attrs: Attrs,
+}
+
+macro_rules! hx_methods {
+    ($($(#[$doc:meta])* $method:ident => $name:literal,)*) => {
+        $(
+            $(#[$doc])*
+            pub fn $method(self, value: impl Render) -> Hx {
+                Hx {
+                    attrs: self.attrs.set($name, value),
+                }
+            }
+        )*
+    };
+}
+
+impl Hx {
+    hx_methods! {
+        /// Sets `hx-get`, the URL to send a `GET` request to.
+        get => "hx-get",
+        /// Sets `hx-post`, the URL to send a `POST` request to.
+        post => "hx-post",
+        /// Sets `hx-put`, the URL to send a `PUT` request to.
+        put => "hx-put",
+        /// Sets `hx-patch`, the URL to send a `PATCH` request to.
+        patch => "hx-patch",
+        /// Sets `hx-delete`, the URL to send a `DELETE` request to.
+        delete => "hx-delete",
+        /// Sets `hx-trigger`, the events that send the request.
+        trigger => "hx-trigger",
+        /// Sets `hx-target`, a selector for the element to update.
+        target => "hx-target",
+        /// Sets `hx-swap`, how the response replaces the target, such as
+        /// `innerHTML` or `beforeend`.
+        swap => "hx-swap",
+        /// Sets `hx-select`, a selector for the part of the response to use.
+        select => "hx-select",
+        /// Sets `hx-push-url`, to add the URL to the browser history.
+        push_url => "hx-push-url",
+        /// Sets `hx-confirm`, a question to confirm before sending.
+        confirm => "hx-confirm",
+        /// Sets `hx-indicator`, a selector for the element shown while the
+        /// request is in flight.
+        indicator => "hx-indicator",
+        /// Sets `hx-include`, a selector for other elements whose values are
+        /// sent.
+        include => "hx-include",
+        /// Sets `hx-vals`, extra values to send, as JSON.
+        vals => "hx-vals",
+        /// Sets `hx-boost`, to turn links and forms inside the element into
+        /// requests.
+        boost => "hx-boost",
+    }
+
+    /// Returns the attributes as an [`Attrs`], to add others that aren't
+    /// covered here.
+    pub fn into_attrs(self) -> Attrs {
+        self.attrs
+    }
+}
@@ -0,0 +1,36 @@
+//! The switch for splice comments, which are compiled in by the `audit`
+//! feature.
+//!
+//! Cargo unifies features across a build, so if any crate turns on `audit`,
+//! every template in the build is compiled with comments, including those in
+//! crates that didn't ask for them. Keeping the comments off until the program
+//! turns them on means that can't change anyone's output by surprise.
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Turns the comments that mark each splice on or off, for the whole
+/// program. They're off until this is called.
+///
+/// Only templates compiled with the `audit` feature have comments to turn
+/// on.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// maud::set_audit(true);
+/// let markup = html! { p { (1 + 1) } };
+/// assert!(markup.into_string().starts_with("<p><!-- splice: "));
+/// ```
+pub fn set_audit(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Whether splice comments are turned on, as checked by the code that
+/// `html!` generates.
+pub fn audit_enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
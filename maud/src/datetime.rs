@@ -0,0 +1,109 @@
+use alloc::string::String;
+
+use crate::{escape, Markup, PreEscaped, Render};
+
+/// Renders a `<time>` element, with a machine-readable `datetime`
+/// attribute and human-readable content.
+///
+/// `datetime` should be in one of the [formats] that HTML accepts, such as
+/// `2011-11-18` or `2011-11-18T14:54:39Z`. It's escaped, as is `display`
+/// (unless it's pre-escaped markup).
+///
+/// With the `chrono` feature, [`timestamp`] formats both parts from a
+/// `chrono` value.
+///
+/// [formats]: https://developer.mozilla.org/en-US/docs/Web/HTML/Element/time#valid_datetime_values
+///
+/// # Example
+///
+/// ```rust
+/// let markup = maud::time_element("2011-11-18", "November 18, 2011");
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<time datetime="2011-11-18">November 18, 2011</time>"#,
+/// );
+/// ```
+pub fn time_element(datetime: impl AsRef<str>, display: impl Render) -> Markup {
+    let mut buffer = String::from("<time datetime=\"");
+    escape::escape_to_string(datetime.as_ref(), &mut buffer);
+    buffer.push_str("\">");
+    display.render_to(&mut buffer);
+    buffer.push_str("</time>");
+    PreEscaped(buffer)
+}
+
+/// A date or time that can be rendered in a `<time>` element.
+///
+/// Requires the `chrono` feature.
+#[cfg(feature = "chrono")]
+pub trait Timestamp {
+    /// Formats the value for the `datetime` attribute.
+    fn machine_readable(&self) -> String;
+
+    /// Formats the value for people to read, in English.
+    fn human_readable(&self) -> String;
+}
+
+/// Renders a `<time>` element for `value`, formatting both the `datetime`
+/// attribute and the content.
+///
+/// Requires the `chrono` feature.
+///
+/// # Example
+///
+/// ```rust
+/// # extern crate chrono_dep as chrono;
+/// use chrono::NaiveDate;
+///
+/// let date = NaiveDate::from_ymd_opt(2011, 11, 18).unwrap();
+/// assert_eq!(
+///     maud::timestamp(&date).into_string(),
+///     r#"<time datetime="2011-11-18">18 November 2011</time>"#,
+/// );
+/// ```
+#[cfg(feature = "chrono")]
+pub fn timestamp(value: &impl Timestamp) -> Markup {
+    time_element(value.machine_readable(), value.human_readable())
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_support {
+    use alloc::string::{String, ToString};
+    use chrono_dep::{DateTime, NaiveDate, NaiveDateTime, SecondsFormat, TimeZone};
+    use core::fmt::Display;
+
+    use super::Timestamp;
+
+    impl<Tz: TimeZone> Timestamp for DateTime<Tz>
+    where
+        Tz::Offset: Display,
+    {
+        fn machine_readable(&self) -> String {
+            self.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        }
+
+        fn human_readable(&self) -> String {
+            self.format("%-d %B %Y, %H:%M").to_string()
+        }
+    }
+
+    impl Timestamp for NaiveDateTime {
+        fn machine_readable(&self) -> String {
+            self.format("%Y-%m-%dT%H:%M:%S%.f").to_string()
+        }
+
+        fn human_readable(&self) -> String {
+            self.format("%-d %B %Y, %H:%M").to_string()
+        }
+    }
+
+    impl Timestamp for NaiveDate {
+        fn machine_readable(&self) -> String {
+            self.format("%Y-%m-%d").to_string()
+        }
+
+        fn human_readable(&self) -> String {
+            self.format("%-d %B %Y").to_string()
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use alloc::string::String;
+
+use crate::{Markup, PreEscaped, Render};
+
+/// Renders `body` as a complete HTML document for the body of an email.
+///
+/// Email clients are stricter than browsers about what comes before the
+/// content: this adds the doctype, and declares the character set with an
+/// `http-equiv` tag as well as a `charset` one, since some clients (notably
+/// Outlook) only read the former. A viewport tag keeps mobile clients from
+/// shrinking the message.
+///
+/// To also move the rules from `<style>` elements into `style` attributes,
+/// use [`email_html_with`].
+///
+/// Requires the `email` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let email = maud::email_html(html! { p { "Your order has shipped!" } });
+///
+/// assert_eq!(
+///     email.into_string(),
+///     concat!(
+///         "<!DOCTYPE html><html><head>",
+///         r#"<meta charset="utf-8">"#,
+///         r#"<meta http-equiv="Content-Type" content="text/html; charset=utf-8">"#,
+///         r#"<meta name="viewport" content="width=device-width, initial-scale=1">"#,
+///         "</head><body><p>Your order has shipped!</p></body></html>",
+///     ),
+/// );
+/// ```
+pub fn email_html(body: impl Render) -> Markup {
+    let mut buffer = String::from(crate::DOCTYPE.0);
+    buffer.push_str(concat!(
+        "<html><head>",
+        r#"<meta charset="utf-8">"#,
+        r#"<meta http-equiv="Content-Type" content="text/html; charset=utf-8">"#,
+        r#"<meta name="viewport" content="width=device-width, initial-scale=1">"#,
+        "</head><body>",
+    ));
+    body.render_to(&mut buffer);
+    buffer.push_str("</body></html>");
+    PreEscaped(buffer)
+}
+
+/// Options for [`email_html_with`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct EmailOptions {
+    /// Moves the rules from each `<style>` element into the `style`
+    /// attribute of every element they match, and removes the `<style>`
+    /// elements. Many email clients ignore `<style>`, but keep inline styles.
+    ///
+    /// Only styles in the document are inlined: stylesheets linked with
+    /// `<link>` aren't fetched.
+    pub inline_css: bool,
+}
+
+/// Renders `body` as a complete HTML document for the body of an email, as
+/// with [`email_html`], and then applies `options`.
+///
+/// Inlining uses the [`css-inline`](https://crates.io/crates/css-inline)
+/// crate, which reads the whole document back in, so the output is
+/// serialized again and may differ in small ways from what the template
+/// wrote. It returns an error if the CSS can't be inlined.
+///
+/// Requires the `email` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, EmailOptions};
+///
+/// let body = html! {
+///     style { "p { color: purple } .small { font-size: 12px }" }
+///     p.small { "Your order has shipped!" }
+/// };
+/// let email = maud::email_html_with(body, EmailOptions { inline_css: true })?;
+///
+/// assert!(email.into_string().ends_with(concat!(
+///     r#"<body><p class="small" style="color: purple;font-size: 12px;">"#,
+///     "Your order has shipped!</p></body></html>",
+/// )));
+/// # Ok::<(), css_inline::InlineError>(())
+/// ```
+pub fn email_html_with(
+    body: impl Render,
+    options: EmailOptions,
+) -> Result<Markup, css_inline::InlineError> {
+    let document = email_html(body);
+    if !options.inline_css {
+        return Ok(document);
+    }
+    let inliner = css_inline::CSSInliner::options()
+        .load_remote_stylesheets(false)
+        .build();
+    Ok(PreEscaped(inliner.inline(&document.0)?))
+}
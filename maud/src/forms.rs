@@ -0,0 +1,185 @@
+use alloc::string::String;
+
+use crate::{escape, Markup, PreEscaped};
+
+/// Renders a list of `<option>` elements, for placing inside a `<select>`.
+///
+/// Each entry in `options` is a `(value, label)` pair. The option whose
+/// value equals `selected`, if any, is marked with the `selected`
+/// attribute. Values and labels are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let ponies = [("pinkie", "Pinkie Pie"), ("rarity", "Rarity")];
+///
+/// let markup = html! {
+///     select name="pony" {
+///         (maud::select_options(&ponies, Some("rarity")))
+///     }
+/// };
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         r#"<select name="pony">"#,
+///         r#"<option value="pinkie">Pinkie Pie</option>"#,
+///         r#"<option value="rarity" selected>Rarity</option>"#,
+///         r#"</select>"#,
+///     ),
+/// );
+/// ```
+pub fn select_options<V: AsRef<str>, L: AsRef<str>>(
+    options: &[(V, L)],
+    selected: Option<&str>,
+) -> Markup {
+    let mut buffer = String::new();
+    for (value, label) in options {
+        let value = value.as_ref();
+        buffer.push_str("<option value=\"");
+        escape::escape_to_string(value, &mut buffer);
+        buffer.push('"');
+        if selected == Some(value) {
+            buffer.push_str(" selected");
+        }
+        buffer.push('>');
+        escape::escape_to_string(label.as_ref(), &mut buffer);
+        buffer.push_str("</option>");
+    }
+    PreEscaped(buffer)
+}
+
+/// Renders a summary of the errors in a submitted form, for placing above
+/// the form.
+///
+/// Each entry in `errors` is a `(field, message)` pair, where `field` is the
+/// ID of the input with the problem. Each message links to its input, so
+/// that keyboard users can jump straight to it. The summary has
+/// `role="alert"`, so screen readers announce it as soon as the page loads.
+/// If there are no errors, nothing is rendered.
+///
+/// Field IDs and messages are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// let errors = [("email", "Enter an email address")];
+///
+/// assert_eq!(
+///     maud::error_summary(&errors).into_string(),
+///     concat!(
+///         r#"<div role="alert"><ul>"#,
+///         r##"<li><a href="#email">Enter an email address</a></li>"##,
+///         "</ul></div>",
+///     ),
+/// );
+/// ```
+pub fn error_summary<F: AsRef<str>, M: AsRef<str>>(errors: &[(F, M)]) -> Markup {
+    let mut buffer = String::new();
+    if errors.is_empty() {
+        return PreEscaped(buffer);
+    }
+    buffer.push_str(r#"<div role="alert"><ul>"#);
+    for (field, message) in errors {
+        buffer.push_str("<li><a href=\"#");
+        escape::escape_to_string(field.as_ref(), &mut buffer);
+        buffer.push_str("\">");
+        escape::escape_to_string(message.as_ref(), &mut buffer);
+        buffer.push_str("</a></li>");
+    }
+    buffer.push_str("</ul></div>");
+    PreEscaped(buffer)
+}
+
+/// Renders a `<label>` and an `<input>`, linked by a generated ID.
+///
+/// The ID is taken from `ids`, and the `attrs` are added to the input as
+/// `(name, value)` pairs. If `attrs` includes an `id`, that is used instead
+/// and nothing is taken from `ids`.
+///
+/// The label, attribute names and attribute values are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::FieldIds;
+///
+/// let mut ids = FieldIds::new("signup");
+/// let markup = maud::labeled_input(&mut ids, "Email", &[("type", "email"), ("name", "email")]);
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         r#"<label for="signup-0">Email</label>"#,
+///         r#"<input id="signup-0" type="email" name="email">"#,
+///     ),
+/// );
+/// ```
+pub fn labeled_input<N: AsRef<str>, V: AsRef<str>>(
+    ids: &mut FieldIds,
+    label: &str,
+    attrs: &[(N, V)],
+) -> Markup {
+    let given_id = attrs
+        .iter()
+        .find(|(name, _)| name.as_ref().eq_ignore_ascii_case("id"))
+        .map(|(_, value)| value.as_ref());
+    let generated_id;
+    let id = match given_id {
+        Some(id) => id,
+        None => {
+            generated_id = ids.next();
+            &generated_id
+        }
+    };
+    let mut buffer = String::from("<label for=\"");
+    escape::escape_to_string(id, &mut buffer);
+    buffer.push_str("\">");
+    escape::escape_to_string(label, &mut buffer);
+    buffer.push_str("</label><input id=\"");
+    escape::escape_to_string(id, &mut buffer);
+    buffer.push('"');
+    for (name, value) in attrs {
+        if given_id.is_some() && name.as_ref().eq_ignore_ascii_case("id") {
+            continue;
+        }
+        buffer.push(' ');
+        escape::escape_to_string(name.as_ref(), &mut buffer);
+        buffer.push_str("=\"");
+        escape::escape_to_string(value.as_ref(), &mut buffer);
+        buffer.push('"');
+    }
+    buffer.push('>');
+    PreEscaped(buffer)
+}
+
+/// A source of element IDs for [`labeled_input`] and [`tabs`](crate::tabs).
+///
+/// IDs are the prefix followed by a number counting up from zero, such as
+/// `signup-0`. They're only unique within one `FieldIds`, so use one per
+/// page, or give each its own prefix.
+#[derive(Debug, Clone)]
+pub struct FieldIds {
+    prefix: String,
+    next: usize,
+}
+
+impl FieldIds {
+    /// Creates a source of IDs that start with `prefix`.
+    pub fn new(prefix: impl Into<String>) -> FieldIds {
+        FieldIds {
+            prefix: prefix.into(),
+            next: 0,
+        }
+    }
+
+    pub(crate) fn next(&mut self) -> String {
+        let mut id = self.prefix.clone();
+        id.push('-');
+        id.push_str(itoa::Buffer::new().format(self.next));
+        self.next += 1;
+        id
+    }
+}
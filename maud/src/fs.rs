@@ -0,0 +1,29 @@
+extern crate std;
+
+use std::{io, path::Path};
+
+use crate::{with_trailing_newline, Render};
+
+/// Renders `markup` and writes it to the file at `path`, followed by a
+/// newline.
+///
+/// The file is created if it doesn't exist, and replaced if it does. The
+/// newline is left out if the markup already ends with one, as with
+/// [`with_trailing_newline`].
+///
+/// Requires the `fs` feature.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use maud::{html, DOCTYPE};
+///
+/// maud::write_html_file(
+///     "public/index.html",
+///     html! { (DOCTYPE) title { "Ponyville" } },
+/// )?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub fn write_html_file(path: impl AsRef<Path>, markup: impl Render) -> io::Result<()> {
+    std::fs::write(path, with_trailing_newline(markup).0)
+}
@@ -0,0 +1,94 @@
+use alloc::string::String;
+use serde_dep::Serialize;
+
+use crate::{escape, Markup, PreEscaped};
+
+/// Serializes `value` as JSON, for embedding in a
+/// `<script type="application/ld+json">` block.
+///
+/// The characters `<`, `>` and `&` are written as `\u` escapes, so the
+/// output can never close the `<script>` element early. As these characters
+/// only appear inside JSON strings, the result is still equivalent JSON.
+///
+/// Requires the `serde` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+/// use serde_json::json;
+///
+/// let schema = json!({
+///     "@context": "https://schema.org",
+///     "@type": "Person",
+///     "name": "</script><script>alert(1)</script>",
+/// });
+///
+/// let markup = html! {
+///     script type="application/ld+json" { (maud::ld_json(&schema)?) }
+/// };
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         r#"<script type="application/ld+json">"#,
+///         r#"{"@context":"https://schema.org","@type":"Person","#,
+///         r#""name":"\u003c/script\u003e\u003cscript\u003ealert(1)\u003c/script\u003e"}"#,
+///         "</script>",
+///     ),
+/// );
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub fn ld_json<T: Serialize + ?Sized>(value: &T) -> Result<Markup, serde_json::Error> {
+    let json = serde_json::to_string(value)?;
+    let mut buffer = String::with_capacity(json.len());
+    for c in json.chars() {
+        match c {
+            '<' => buffer.push_str("\\u003c"),
+            '>' => buffer.push_str("\\u003e"),
+            '&' => buffer.push_str("\\u0026"),
+            _ => buffer.push(c),
+        }
+    }
+    Ok(PreEscaped(buffer))
+}
+
+/// Serializes `value` as pretty-printed JSON, indented by two spaces, for
+/// showing in a `<pre>` element on a debug or admin page.
+///
+/// The JSON is escaped as usual for text, so unlike [`ld_json`] it must not
+/// be placed inside a `<script>`.
+///
+/// Requires the `serde` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+/// use serde_json::json;
+///
+/// let payload = json!({ "name": "<Rarity>", "tags": ["fashion"] });
+///
+/// let markup = html! {
+///     pre { (maud::json_pretty(&payload)?) }
+/// };
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         "<pre>{\n",
+///         "  &quot;name&quot;: &quot;&lt;Rarity&gt;&quot;,\n",
+///         "  &quot;tags&quot;: [\n",
+///         "    &quot;fashion&quot;\n",
+///         "  ]\n",
+///         "}</pre>",
+///     ),
+/// );
+/// # Ok::<(), serde_json::Error>(())
+/// ```
+pub fn json_pretty<T: Serialize + ?Sized>(value: &T) -> Result<Markup, serde_json::Error> {
+    let json = serde_json::to_string_pretty(value)?;
+    let mut buffer = String::with_capacity(json.len());
+    escape::escape_to_string(&json, &mut buffer);
+    Ok(PreEscaped(buffer))
+}
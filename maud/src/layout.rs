@@ -0,0 +1,296 @@
+use alloc::{collections::BTreeMap, string::String, sync::Arc};
+use core::cell::RefCell;
+
+use crate::{escape, Markup, PreEscaped, Render};
+
+/// The placeholder that [`Layout::prerender`] passes in place of the
+/// content.
+const CONTENT_MARKER: &str = "<!--maud:content-->";
+
+/// A page layout that wraps some content, such as a common header and
+/// footer.
+///
+/// This trait is implemented for any closure that takes the content and
+/// returns the complete page.
+///
+/// If the layout doesn't depend on the content, then it can be rendered
+/// once with [`.prerender()`](Layout::prerender), and the resulting
+/// [`Shell`] reused for every page.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Layout, Markup};
+///
+/// let layout = |content: Markup| html! {
+///     header { "Ponyville Times" }
+///     main { (content) }
+/// };
+///
+/// let shell = layout.prerender();
+///
+/// assert_eq!(
+///     shell.fill(html! { p { "Hello!" } }).into_string(),
+///     "<header>Ponyville Times</header><main><p>Hello!</p></main>",
+/// );
+/// ```
+pub trait Layout {
+    /// Renders the layout around `content`.
+    fn layout(&self, content: Markup) -> Markup;
+
+    /// Renders the layout once, with a placeholder in place of the
+    /// content, and splits it into a reusable [`Shell`].
+    ///
+    /// # Panics
+    ///
+    /// Panics if the layout doesn't render its content exactly once.
+    fn prerender(&self) -> Shell {
+        let markup = self.layout(PreEscaped(CONTENT_MARKER.into()));
+        Shell::split(markup, CONTENT_MARKER).expect("layout should render its content exactly once")
+    }
+}
+
+impl<F: Fn(Markup) -> Markup> Layout for F {
+    fn layout(&self, content: Markup) -> Markup {
+        self(content)
+    }
+}
+
+/// A pre-rendered layout, split into the markup before and after the
+/// content.
+///
+/// Cloning a `Shell` is cheap, as both halves are reference counted.
+#[derive(Clone, Debug)]
+pub struct Shell {
+    before: Arc<str>,
+    after: Arc<str>,
+}
+
+impl Shell {
+    /// Splits `markup` at `marker`, which is removed.
+    ///
+    /// Returns `None` if `marker` doesn't appear in `markup` exactly once.
+    pub fn split<T: AsRef<str>>(markup: PreEscaped<T>, marker: &str) -> Option<Shell> {
+        let (before, after) = markup.0.as_ref().split_once(marker)?;
+        if after.contains(marker) {
+            return None;
+        }
+        Some(Shell {
+            before: before.into(),
+            after: after.into(),
+        })
+    }
+
+    /// Renders `content` inside the shell.
+    pub fn fill(&self, content: impl Render) -> Markup {
+        let mut buffer = String::from(&*self.before);
+        content.render_to(&mut buffer);
+        buffer.push_str(&self.after);
+        PreEscaped(buffer)
+    }
+}
+
+/// A part of a layout that a page can override, as with blocks in Jinja or
+/// Django templates.
+///
+/// Give the layout a struct with a `Block` field for each part, and render
+/// each one with `@block(field) { ... }`. The body of the `@block` is the
+/// default content, which is rendered unless the block was overridden with
+/// [`Block::new`].
+///
+/// As `Block::default()` doesn't override anything, a page can derive
+/// `Default` for the struct and use struct update syntax to inherit every
+/// block it doesn't set. A misspelled block is then a compile error, like
+/// any other missing field.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Block, Markup};
+///
+/// #[derive(Default)]
+/// struct Base {
+///     title: Block,
+///     content: Block,
+///     footer: Block,
+/// }
+///
+/// fn base(page: Base) -> Markup {
+///     html! {
+///         h1 { @block(page.title) { "Ponyville Times" } }
+///         main { @block(page.content) {} }
+///         footer { @block(page.footer) { "© Ponyville" } }
+///     }
+/// }
+///
+/// let page = base(Base {
+///     content: Block::new(html! { p { "Hello!" } }),
+///     ..Base::default()
+/// });
+///
+/// assert_eq!(
+///     page.into_string(),
+///     concat!(
+///         "<h1>Ponyville Times</h1>",
+///         "<main><p>Hello!</p></main>",
+///         "<footer>© Ponyville</footer>",
+///     ),
+/// );
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Block {
+    content: Option<Markup>,
+}
+
+impl Block {
+    /// Creates a block that overrides the default with `content`.
+    pub fn new(content: impl Render) -> Block {
+        Block {
+            content: Some(content.render()),
+        }
+    }
+
+    /// Returns the content that overrides the default, if any.
+    pub fn get(&self) -> Option<&Markup> {
+        self.content.as_ref()
+    }
+
+    /// Returns this block if it was overridden, or `fallback` otherwise.
+    ///
+    /// This is for a layout that extends another one: it can pass on a
+    /// page's block, with its own content as the fallback.
+    pub fn or(self, fallback: Block) -> Block {
+        if self.content.is_some() {
+            self
+        } else {
+            fallback
+        }
+    }
+}
+
+/// Renders a complete HTML document: the doctype, then an `<html>` element
+/// holding `head` and `body`.
+///
+/// The `lang` attribute is set to `lang`, or `"en"` if that's `None`. It is
+/// escaped; `head` and `body` are rendered as usual.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let page = maud::html_document(
+///     Some("fr"),
+///     html! { title { "Bonjour" } },
+///     html! { p { "Salut !" } },
+/// );
+///
+/// assert_eq!(
+///     page.into_string(),
+///     concat!(
+///         r#"<!DOCTYPE html><html lang="fr">"#,
+///         "<head><title>Bonjour</title></head>",
+///         "<body><p>Salut !</p></body></html>",
+///     ),
+/// );
+/// ```
+pub fn html_document(lang: Option<&str>, head: impl Render, body: impl Render) -> Markup {
+    let mut buffer = String::from(crate::DOCTYPE.0);
+    buffer.push_str("<html lang=\"");
+    escape::escape_to_string(lang.unwrap_or("en"), &mut buffer);
+    buffer.push_str("\"><head>");
+    head.render_to(&mut buffer);
+    buffer.push_str("</head><body>");
+    body.render_to(&mut buffer);
+    buffer.push_str("</body></html>");
+    PreEscaped(buffer)
+}
+
+/// Renders `markup`, followed by a newline unless it already ends with one.
+///
+/// POSIX tools expect a text file to end with a newline, which the `html!`
+/// macro never writes. This is for pages that are saved as static files.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let page = maud::with_trailing_newline(html! { p { "Hi!" } });
+/// assert_eq!(page.into_string(), "<p>Hi!</p>\n");
+/// ```
+pub fn with_trailing_newline(markup: impl Render) -> Markup {
+    let mut buffer = markup.render().0;
+    if !buffer.ends_with('\n') {
+        buffer.push('\n');
+    }
+    PreEscaped(buffer)
+}
+
+/// Named buffers for collecting markup from anywhere in a page, to be
+/// rendered somewhere else.
+///
+/// This is useful for hoisting a component's `<script>` and `<style>`
+/// dependencies into the `<head>`. In a template, `@slot(slots, name) { ... }`
+/// appends its body to a slot instead of rendering it, and
+/// `@outlet(slots, name)` renders everything collected in a slot so far.
+///
+/// As templates render from top to bottom, an outlet only sees what was
+/// added before it. So render the parts of the page that fill the slots
+/// first, then the layout that contains the outlets.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Markup, Slots};
+///
+/// fn chart(slots: &Slots) -> Markup {
+///     html! {
+///         @slot(slots, "head") {
+///             script src="/chart.js" {}
+///         }
+///         canvas #chart {}
+///     }
+/// }
+///
+/// let slots = Slots::new();
+/// let body = chart(&slots);
+/// let page = html! {
+///     head { @outlet(slots, "head") }
+///     body { (body) }
+/// };
+///
+/// assert_eq!(
+///     page.into_string(),
+///     concat!(
+///         r#"<head><script src="/chart.js"></script></head>"#,
+///         r#"<body><canvas id="chart"></canvas></body>"#,
+///     ),
+/// );
+/// ```
+#[derive(Debug, Default)]
+pub struct Slots {
+    buffers: RefCell<BTreeMap<String, String>>,
+}
+
+impl Slots {
+    /// Creates a set of empty slots.
+    pub fn new() -> Slots {
+        Slots::default()
+    }
+
+    /// Appends `content` to the named slot.
+    pub fn append(&self, name: &str, content: impl Render) {
+        let mut buffers = self.buffers.borrow_mut();
+        let buffer = match buffers.get_mut(name) {
+            Some(buffer) => buffer,
+            None => buffers.entry(name.into()).or_default(),
+        };
+        content.render_to(buffer);
+    }
+
+    /// Removes and returns everything in the named slot.
+    pub fn take(&self, name: &str) -> Markup {
+        PreEscaped(self.buffers.borrow_mut().remove(name).unwrap_or_default())
+    }
+}
@@ -11,12 +11,75 @@
 
 extern crate alloc;
 
-use alloc::{borrow::Cow, boxed::Box, string::String, sync::Arc};
+use alloc::{borrow::Cow, boxed::Box, rc::Rc, string::String, sync::Arc, vec::Vec};
 use core::fmt::{self, Arguments, Display, Write};
 
-pub use maud_macros::html;
+pub use maud_macros::{
+    html, html_fmt, html_lazy, html_minify, html_single_quoted, html_static, html_with, MaudForm,
+};
 
+#[cfg(feature = "bumpalo")]
+pub use maud_macros::html_in;
+
+#[cfg(feature = "alloc-counting")]
+mod alloc_count;
+mod attrs;
+#[cfg(feature = "audit")]
+mod audit;
+mod datetime;
+#[cfg(feature = "email")]
+mod email;
 mod escape;
+mod forms;
+#[cfg(feature = "fs")]
+mod fs;
+#[cfg(feature = "serde")]
+mod json;
+mod layout;
+mod locale;
+#[cfg(feature = "markdown")]
+mod markdown;
+#[cfg(feature = "debug-nesting")]
+mod nesting;
+#[cfg(feature = "scoped-css")]
+mod scoped;
+mod sse;
+mod text;
+#[cfg(feature = "validation")]
+mod validate;
+mod widgets;
+
+pub use crate::{
+    attrs::{cx, enum_class, hx, srcset, Attrs, Hx},
+    datetime::time_element,
+    forms::{error_summary, labeled_input, select_options, FieldIds},
+    layout::{html_document, with_trailing_newline, Block, Layout, Shell, Slots},
+    locale::{date, num, Date, Locale, Localizer, Number},
+    sse::sse_data,
+    text::{highlight, plural, plural_fmt, strip_tags, trim_trailing, wbr_every},
+    widgets::{
+        avatar, breadcrumbs, details, icon, kbd_combo, live_region, meta_refresh, meta_tags,
+        page_head, pagination, preload_links, progress, skip_link, tabs, with_noscript, IconSet,
+        KbdCombo, PageMeta, Pagination, Politeness, Progress, SkipLink,
+    },
+};
+
+#[cfg(feature = "audit")]
+pub use crate::audit::set_audit;
+#[cfg(feature = "chrono")]
+pub use crate::datetime::{timestamp, Timestamp};
+#[cfg(feature = "email")]
+pub use crate::email::{email_html, email_html_with, EmailOptions};
+#[cfg(feature = "fs")]
+pub use crate::fs::write_html_file;
+#[cfg(feature = "serde")]
+pub use crate::json::{json_pretty, ld_json};
+#[cfg(feature = "markdown")]
+pub use crate::markdown::{markdown, Markdown, MarkdownOptions};
+#[cfg(feature = "scoped-css")]
+pub use crate::scoped::{scoped, ScopeIds};
+#[cfg(feature = "validation")]
+pub use crate::validate::{validate_html, ValidationError, ValidationErrorKind};
 
 /// An adapter that escapes HTML special characters.
 ///
@@ -86,6 +149,11 @@ impl fmt::Write for Escaper<'_> {
 ///     }
 /// }
 /// ```
+#[diagnostic::on_unimplemented(
+    message = "`{Self}` does not implement `maud::Render`",
+    label = "doesn't implement `Render`",
+    note = "a value spliced into a template must implement `maud::Render`, or `Display` to be escaped as text"
+)]
 pub trait Render {
     /// Renders `self` as a block of `Markup`.
     fn render(&self) -> Markup {
@@ -106,24 +174,103 @@ pub trait Render {
     fn render_to(&self, buffer: &mut String) {
         buffer.push_str(&self.render().into_string());
     }
+
+    /// Writes a representation of `self` to any [`fmt::Write`], returning
+    /// an error if the writer fails.
+    ///
+    /// Its default implementation renders into a `String` first, then
+    /// writes that out in one go.
+    fn render_to_fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        w.write_str(&self.render().0)
+    }
+
+    /// Returns the length in bytes of the rendered output, such as for a
+    /// `Content-Length` header when the output is streamed.
+    ///
+    /// Its default implementation renders `self` and measures the result.
+    /// Strings, numbers and pre-escaped markup override it to measure
+    /// without rendering, so it's cheap for a static template, such as one
+    /// from `html_static!`. If you override it, it must agree with
+    /// `.render_to()`.
+    fn rendered_len(&self) -> usize {
+        self.render().0.len()
+    }
+}
+
+/// Returns the length of `input` once it's escaped.
+fn escaped_len(input: &str) -> usize {
+    input
+        .bytes()
+        .map(|b| match b {
+            b'&' => "&amp;".len(),
+            b'<' | b'>' => "&lt;".len(),
+            b'"' => "&quot;".len(),
+            _ => 1,
+        })
+        .sum()
 }
 
 impl Render for str {
     fn render_to(&self, w: &mut String) {
         escape::escape_to_string(self, w);
     }
+
+    fn rendered_len(&self) -> usize {
+        escaped_len(self)
+    }
 }
 
 impl Render for String {
     fn render_to(&self, w: &mut String) {
         str::render_to(self, w);
     }
+
+    fn rendered_len(&self) -> usize {
+        escaped_len(self)
+    }
 }
 
 impl Render for Cow<'_, str> {
     fn render_to(&self, w: &mut String) {
         str::render_to(self, w);
     }
+
+    fn rendered_len(&self) -> usize {
+        escaped_len(self)
+    }
+}
+
+/// Renders each element in turn, with nothing in between.
+impl<T: Render> Render for [T] {
+    fn render_to(&self, w: &mut String) {
+        for item in self {
+            item.render_to(w);
+        }
+    }
+
+    fn rendered_len(&self) -> usize {
+        self.iter().map(T::rendered_len).sum()
+    }
+}
+
+impl<T: Render> Render for Vec<T> {
+    fn render_to(&self, w: &mut String) {
+        <[T]>::render_to(self, w);
+    }
+
+    fn rendered_len(&self) -> usize {
+        <[T]>::rendered_len(self)
+    }
+}
+
+impl<T: Render + Clone> Render for Cow<'_, [T]> {
+    fn render_to(&self, w: &mut String) {
+        <[T]>::render_to(self, w);
+    }
+
+    fn rendered_len(&self) -> usize {
+        <[T]>::rendered_len(self)
+    }
 }
 
 impl Render for Arguments<'_> {
@@ -136,24 +283,70 @@ impl<T: Render + ?Sized> Render for &T {
     fn render_to(&self, w: &mut String) {
         T::render_to(self, w);
     }
+
+    fn render_to_fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        T::render_to_fmt(self, w)
+    }
+
+    fn rendered_len(&self) -> usize {
+        T::rendered_len(self)
+    }
 }
 
 impl<T: Render + ?Sized> Render for &mut T {
     fn render_to(&self, w: &mut String) {
         T::render_to(self, w);
     }
+
+    fn render_to_fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        T::render_to_fmt(self, w)
+    }
+
+    fn rendered_len(&self) -> usize {
+        T::rendered_len(self)
+    }
 }
 
 impl<T: Render + ?Sized> Render for Box<T> {
     fn render_to(&self, w: &mut String) {
         T::render_to(self, w);
     }
+
+    fn render_to_fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        T::render_to_fmt(self, w)
+    }
+
+    fn rendered_len(&self) -> usize {
+        T::rendered_len(self)
+    }
+}
+
+impl<T: Render + ?Sized> Render for Rc<T> {
+    fn render_to(&self, w: &mut String) {
+        T::render_to(self, w);
+    }
+
+    fn render_to_fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        T::render_to_fmt(self, w)
+    }
+
+    fn rendered_len(&self) -> usize {
+        T::rendered_len(self)
+    }
 }
 
 impl<T: Render + ?Sized> Render for Arc<T> {
     fn render_to(&self, w: &mut String) {
         T::render_to(self, w);
     }
+
+    fn render_to_fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        T::render_to_fmt(self, w)
+    }
+
+    fn rendered_len(&self) -> usize {
+        T::rendered_len(self)
+    }
 }
 
 macro_rules! impl_render_with_display {
@@ -180,6 +373,10 @@ macro_rules! impl_render_with_itoa {
                 fn render_to(&self, w: &mut String) {
                     w.push_str(itoa::Buffer::new().format(*self));
                 }
+
+                fn rendered_len(&self) -> usize {
+                    itoa::Buffer::new().format(*self).len()
+                }
             }
         )*
     };
@@ -220,13 +417,43 @@ pub fn display(value: impl Display) -> impl Render {
 }
 
 /// A wrapper that renders the inner value without escaping.
+///
+/// With the `paranoid` feature, rendering panics in debug builds if the
+/// value contains a control character that HTML doesn't allow, such as NUL.
+/// Tabs, newlines, form feeds and carriage returns are fine.
 #[derive(Debug, Clone, Copy)]
 pub struct PreEscaped<T>(pub T);
 
 impl<T: AsRef<str>> Render for PreEscaped<T> {
     fn render_to(&self, w: &mut String) {
+        #[cfg(feature = "paranoid")]
+        check_control_chars(self.0.as_ref());
         w.push_str(self.0.as_ref());
     }
+
+    fn render_to_fmt(&self, w: &mut dyn fmt::Write) -> fmt::Result {
+        #[cfg(feature = "paranoid")]
+        check_control_chars(self.0.as_ref());
+        w.write_str(self.0.as_ref())
+    }
+
+    fn rendered_len(&self) -> usize {
+        self.0.as_ref().len()
+    }
+}
+
+/// Panics if `s` contains a control character other than ASCII whitespace.
+/// The check is skipped in release builds.
+#[cfg(feature = "paranoid")]
+fn check_control_chars(s: &str) {
+    if cfg!(debug_assertions) {
+        if let Some(c) = s
+            .chars()
+            .find(|&c| c.is_control() && !matches!(c, '\t' | '\n' | '\x0C' | '\r'))
+        {
+            panic!("pre-escaped content contains control character {:?}", c);
+        }
+    }
 }
 
 /// A block of markup is a string that does not need to be escaped.
@@ -253,6 +480,68 @@ impl<T: Default> Default for PreEscaped<T> {
     }
 }
 
+/// A string type that `html!` can build its output in, instead of a
+/// `String`.
+///
+/// Write the type at the start of the template, as `html!(buffer = Type,
+/// ...)`, and the result is a `PreEscaped<Type>`. This lets a template
+/// render into a small-string type, such as `CompactString`, which keeps
+/// short output on the stack. As the trait is defined here, you can
+/// implement it for a type from another crate.
+///
+/// Splices are still rendered into a `String` first, then copied over.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Buffer};
+///
+/// /// A string of up to 32 bytes, stored inline.
+/// struct Inline {
+///     bytes: [u8; 32],
+///     len: usize,
+/// }
+///
+/// impl Buffer for Inline {
+///     fn with_capacity(_: usize) -> Inline {
+///         Inline { bytes: [0; 32], len: 0 }
+///     }
+///
+///     fn push_str(&mut self, s: &str) {
+///         self.bytes[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+///         self.len += s.len();
+///     }
+/// }
+///
+/// impl AsRef<str> for Inline {
+///     fn as_ref(&self) -> &str {
+///         std::str::from_utf8(&self.bytes[..self.len]).unwrap()
+///     }
+/// }
+///
+/// let markup = html!(buffer = Inline, b { "Hi!" });
+/// assert_eq!(markup.0.as_ref(), "<b>Hi!</b>");
+/// ```
+pub trait Buffer {
+    /// Creates an empty buffer. `capacity` is a guess at the length of the
+    /// output, based on the size of the template; a small-string type may
+    /// ignore it.
+    fn with_capacity(capacity: usize) -> Self;
+
+    /// Appends `s` to the end of the buffer.
+    fn push_str(&mut self, s: &str);
+}
+
+impl Buffer for String {
+    fn with_capacity(capacity: usize) -> String {
+        String::with_capacity(capacity)
+    }
+
+    fn push_str(&mut self, s: &str) {
+        String::push_str(self, s);
+    }
+}
+
 /// The literal string `<!DOCTYPE html>`.
 ///
 /// # Example
@@ -277,6 +566,77 @@ impl<T: Default> Default for PreEscaped<T> {
 /// ```
 pub const DOCTYPE: PreEscaped<&'static str> = PreEscaped("<!DOCTYPE html>");
 
+/// A UTF-8 byte order mark.
+///
+/// Some tools expect this at the start of a downloaded file. See
+/// [`document`] for an example.
+pub const BOM: PreEscaped<&'static str> = PreEscaped("\u{FEFF}");
+
+/// An XML declaration, for the start of an XML or XHTML document.
+pub const XML_DECLARATION: PreEscaped<&'static str> =
+    PreEscaped(r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+
+/// Renders `body`, with `prelude` placed verbatim before it.
+///
+/// This is useful for the [`BOM`] and [`XML_DECLARATION`] constants, which
+/// must come before anything else in a file. As the prelude is wrapped in
+/// [`PreEscaped`], it is never escaped.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, XML_DECLARATION};
+///
+/// let feed = maud::document(XML_DECLARATION, html! {
+///     feed xmlns="http://www.w3.org/2005/Atom" {
+///         title { "Pinkie's parties" }
+///     }
+/// });
+///
+/// assert_eq!(
+///     feed.into_string(),
+///     concat!(
+///         r#"<?xml version="1.0" encoding="UTF-8"?>"#,
+///         r#"<feed xmlns="http://www.w3.org/2005/Atom"><title>Pinkie's parties</title></feed>"#,
+///     ),
+/// );
+/// ```
+pub fn document<T: AsRef<str>>(prelude: PreEscaped<T>, body: impl Render) -> Markup {
+    let mut buffer = String::from(prelude.0.as_ref());
+    body.render_to(&mut buffer);
+    PreEscaped(buffer)
+}
+
+/// Renders each item separately, returning one block of `Markup` per item.
+///
+/// This is handy when the rendered pieces need to be rearranged or wrapped
+/// individually before being spliced back into a template.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let items = maud::collect(["<Applejack>", "Rarity"]);
+/// let markup = html! {
+///     @for item in &items {
+///         li { (item) }
+///     }
+/// };
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     "<li>&lt;Applejack&gt;</li><li>Rarity</li>",
+/// );
+/// ```
+pub fn collect<I>(items: I) -> Vec<Markup>
+where
+    I: IntoIterator,
+    I::Item: Render,
+{
+    items.into_iter().map(|item| item.render()).collect()
+}
+
 #[cfg(feature = "rocket")]
 mod rocket_support {
     extern crate std;
@@ -412,11 +772,33 @@ mod submillisecond_support {
     }
 }
 
+#[cfg(feature = "bumpalo")]
+mod bumpalo_support {
+    use crate::Render;
+    use alloc::string::String;
+    use bumpalo_dep::collections::String as BumpString;
+
+    impl Render for BumpString<'_> {
+        fn render_to(&self, w: &mut String) {
+            str::render_to(self, w);
+        }
+    }
+}
+
 #[doc(hidden)]
 pub mod macro_private {
-    use crate::{display, Render};
-    use alloc::string::String;
-    use core::fmt::Display;
+    #[cfg(feature = "alloc-counting")]
+    pub use crate::alloc_count::CountingString;
+    #[cfg(feature = "audit")]
+    pub use crate::audit::audit_enabled;
+    #[cfg(feature = "debug-nesting")]
+    pub use crate::nesting::{enter_element, exit_element};
+    use crate::{display, Attrs, Block, Buffer, Hx, Markup, PreEscaped, Render};
+    pub use alloc::string::String;
+    #[cfg(feature = "bumpalo")]
+    pub use bumpalo_dep as bumpalo;
+    use core::borrow::Borrow;
+    use core::fmt::{Display, Write};
 
     #[doc(hidden)]
     #[macro_export]
@@ -424,17 +806,203 @@ pub mod macro_private {
         ($x:expr, $buffer:expr) => {{
             use $crate::macro_private::*;
             match ChooseRenderOrDisplay($x) {
-                x => (&&x).implements_render_or_display().render_to(x.0, $buffer),
+                x => (&&&x)
+                    .implements_render_or_display()
+                    .render_to(x.0, $buffer),
             }
         }};
     }
 
     pub use render_to;
 
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! render_to_single_quoted {
+        ($x:expr, $buffer:expr) => {{
+            let buffer: &mut $crate::macro_private::String = $buffer;
+            let start = buffer.len();
+            $crate::macro_private::render_to!($x, buffer);
+            $crate::macro_private::escape_single_quotes(buffer, start);
+        }};
+    }
+
+    pub use render_to_single_quoted;
+
+    #[doc(hidden)]
+    #[macro_export]
+    macro_rules! render_raw_to {
+        ($x:expr, $buffer:expr) => {{
+            use $crate::macro_private::*;
+            match ChooseRaw($x) {
+                x => (&&&x).raw_kind().render_to(x.0, $buffer),
+            }
+        }};
+    }
+
+    pub use render_raw_to;
+
+    /// A value that can be spliced as raw attributes: a pre-built
+    /// `PreEscaped` string, or an `Attrs`.
+    pub trait RawAttrs {
+        fn push_attrs_to(&self, buffer: &mut String);
+    }
+
+    impl<T: AsRef<str>> RawAttrs for PreEscaped<T> {
+        fn push_attrs_to(&self, buffer: &mut String) {
+            self.render_to(buffer);
+        }
+    }
+
+    impl RawAttrs for Attrs {
+        fn push_attrs_to(&self, buffer: &mut String) {
+            self.push_to(buffer);
+        }
+    }
+
+    impl RawAttrs for Hx {
+        fn push_attrs_to(&self, buffer: &mut String) {
+            self.attrs.push_to(buffer);
+        }
+    }
+
+    impl<T: RawAttrs + ?Sized> RawAttrs for &T {
+        fn push_attrs_to(&self, buffer: &mut String) {
+            T::push_attrs_to(self, buffer);
+        }
+    }
+
+    /// Writes a set of raw attributes, preceded by a space unless there
+    /// aren't any.
+    pub fn push_raw_attrs<T: RawAttrs + ?Sized>(attrs: &T, buffer: &mut String) {
+        let start = buffer.len();
+        buffer.push(' ');
+        attrs.push_attrs_to(buffer);
+        if buffer.len() == start + 1 {
+            buffer.truncate(start);
+        }
+    }
+
+    /// The condition in a `[...]` toggle, which is on if it's `true` or
+    /// `Some`.
+    pub trait Toggle {
+        fn is_on(&self) -> bool;
+    }
+
+    impl Toggle for bool {
+        fn is_on(&self) -> bool {
+            *self
+        }
+    }
+
+    impl<T> Toggle for Option<T> {
+        fn is_on(&self) -> bool {
+            self.is_some()
+        }
+    }
+
+    impl<T: Toggle + ?Sized> Toggle for &T {
+        fn is_on(&self) -> bool {
+            T::is_on(self)
+        }
+    }
+
+    /// The output buffer of `html_fmt!`. After the first error from the
+    /// writer, nothing more is written, and the error is returned by
+    /// `finish()`.
+    pub struct FmtWriter<'a> {
+        inner: &'a mut dyn core::fmt::Write,
+        result: core::fmt::Result,
+    }
+
+    impl<'a> FmtWriter<'a> {
+        pub fn new(inner: &'a mut dyn core::fmt::Write) -> FmtWriter<'a> {
+            FmtWriter {
+                inner,
+                result: Ok(()),
+            }
+        }
+
+        pub fn push_str(&mut self, s: &str) {
+            if self.result.is_ok() {
+                self.result = self.inner.write_str(s);
+            }
+        }
+
+        pub fn finish(self) -> core::fmt::Result {
+            self.result
+        }
+    }
+
+    /// The output buffer of `html!(buffer = Type, ...)`.
+    pub struct BufferWriter<B>(B);
+
+    impl<B: Buffer> BufferWriter<B> {
+        pub fn with_capacity(capacity: usize) -> BufferWriter<B> {
+            BufferWriter(B::with_capacity(capacity))
+        }
+
+        pub fn push_str(&mut self, s: &str) {
+            self.0.push_str(s);
+        }
+
+        pub fn finish(self) -> B {
+            self.0
+        }
+    }
+
+    /// The content of the block in a `@block`, which may be a `Block` or a
+    /// reference to one.
+    pub fn block_content<B: Borrow<Block>>(block: &B) -> Option<&Markup> {
+        block.borrow().get()
+    }
+
+    /// Wraps the iterator of a `@for` loop that uses `@first` or `@last`,
+    /// pairing each item with whether it's the first and the last.
+    pub fn loop_positions<I: IntoIterator>(iter: I) -> LoopPositions<I::IntoIter> {
+        LoopPositions {
+            inner: iter.into_iter().peekable(),
+            first: true,
+        }
+    }
+
+    pub struct LoopPositions<I: Iterator> {
+        inner: core::iter::Peekable<I>,
+        first: bool,
+    }
+
+    impl<I: Iterator> Iterator for LoopPositions<I> {
+        type Item = (bool, bool, I::Item);
+
+        fn next(&mut self) -> Option<Self::Item> {
+            let item = self.inner.next()?;
+            let first = core::mem::replace(&mut self.first, false);
+            let last = self.inner.peek().is_none();
+            Some((first, last, item))
+        }
+    }
+
+    /// Escapes any single quotes written to `buffer` after `start`, so that
+    /// the output can be placed in a single-quoted attribute.
+    pub fn escape_single_quotes(buffer: &mut String, start: usize) {
+        if buffer[start..].contains('\'') {
+            let tail = buffer.split_off(start);
+            for (i, part) in tail.split('\'').enumerate() {
+                if i > 0 {
+                    buffer.push_str("&#39;");
+                }
+                buffer.push_str(part);
+            }
+        }
+    }
+
+    /// Picks how a splice is written: through its `Render` impl, then
+    /// through its `Display` impl with escaping. If there's neither, the
+    /// last choice asks for `Render`, so that the error names the type.
     pub struct ChooseRenderOrDisplay<T>(pub T);
 
     pub struct ViaRenderTag;
     pub struct ViaDisplayTag;
+    pub struct ViaNeitherTag;
 
     pub trait ViaRender {
         fn implements_render_or_display(&self) -> ViaRenderTag {
@@ -446,9 +1014,15 @@ pub mod macro_private {
             ViaDisplayTag
         }
     }
+    pub trait ViaNeither {
+        fn implements_render_or_display(&self) -> ViaNeitherTag {
+            ViaNeitherTag
+        }
+    }
 
-    impl<T: Render> ViaRender for &ChooseRenderOrDisplay<T> {}
-    impl<T: Display> ViaDisplay for ChooseRenderOrDisplay<T> {}
+    impl<T: Render> ViaRender for &&ChooseRenderOrDisplay<T> {}
+    impl<T: Display> ViaDisplay for &ChooseRenderOrDisplay<T> {}
+    impl<T> ViaNeither for ChooseRenderOrDisplay<T> {}
 
     impl ViaRenderTag {
         pub fn render_to<T: Render + ?Sized>(self, value: &T, buffer: &mut String) {
@@ -461,4 +1035,57 @@ pub mod macro_private {
             display(value).render_to(buffer);
         }
     }
+
+    impl ViaNeitherTag {
+        pub fn render_to<T: Render + ?Sized>(self, value: &T, buffer: &mut String) {
+            value.render_to(buffer);
+        }
+    }
+
+    /// Picks how a splice in a `@raw_block` is written: strings as they
+    /// are, then `Display` types without escaping, and anything else through
+    /// its `Render` impl.
+    pub struct ChooseRaw<T>(pub T);
+
+    pub struct RawStrTag;
+    pub struct RawDisplayTag;
+    pub struct RawRenderTag;
+
+    pub trait RawViaStr {
+        fn raw_kind(&self) -> RawStrTag {
+            RawStrTag
+        }
+    }
+    pub trait RawViaDisplay {
+        fn raw_kind(&self) -> RawDisplayTag {
+            RawDisplayTag
+        }
+    }
+    pub trait RawViaRender {
+        fn raw_kind(&self) -> RawRenderTag {
+            RawRenderTag
+        }
+    }
+
+    impl<T: AsRef<str>> RawViaStr for &&ChooseRaw<T> {}
+    impl<T: Display> RawViaDisplay for &ChooseRaw<T> {}
+    impl<T> RawViaRender for ChooseRaw<T> {}
+
+    impl RawStrTag {
+        pub fn render_to<T: AsRef<str> + ?Sized>(self, value: &T, buffer: &mut String) {
+            buffer.push_str(value.as_ref());
+        }
+    }
+
+    impl RawDisplayTag {
+        pub fn render_to<T: Display + ?Sized>(self, value: &T, buffer: &mut String) {
+            let _ = write!(buffer, "{}", value);
+        }
+    }
+
+    impl RawRenderTag {
+        pub fn render_to<T: Render + ?Sized>(self, value: &T, buffer: &mut String) {
+            value.render_to(buffer);
+        }
+    }
 }
@@ -0,0 +1,165 @@
+use alloc::string::String;
+use core::fmt::Write;
+
+use crate::{datetime::time_element, escape, Markup, PreEscaped};
+
+/// Formats numbers and dates for a particular language or region.
+///
+/// Maud doesn't come with any locales, so you can use whichever formatting
+/// rules (or library) suit your site. The output of each method is plain
+/// text, which is escaped when rendered.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Date, Locale, Localizer};
+///
+/// struct German;
+///
+/// impl Locale for German {
+///     fn format_int(&self, value: i128) -> String {
+///         value.to_string()
+///     }
+///
+///     fn format_float(&self, value: f64) -> String {
+///         value.to_string().replace('.', ",")
+///     }
+///
+///     fn format_date(&self, date: Date) -> String {
+///         format!("{}.{}.{}", date.day, date.month, date.year)
+///     }
+/// }
+///
+/// let l10n = Localizer::new(&German);
+/// let markup = html! {
+///     p { (l10n.num(2.5)) " kg, " (l10n.date(Date::new(2011, 11, 18))) }
+/// };
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<p>2,5 kg, <time datetime="2011-11-18">18.11.2011</time></p>"#,
+/// );
+/// ```
+pub trait Locale {
+    /// Formats an integer.
+    fn format_int(&self, value: i128) -> String;
+
+    /// Formats a floating-point number.
+    fn format_float(&self, value: f64) -> String;
+
+    /// Formats a date.
+    fn format_date(&self, date: Date) -> String;
+}
+
+/// A calendar date, for [`date`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Date {
+    /// The year, such as 2011.
+    pub year: i32,
+    /// The month, from 1 to 12.
+    pub month: u8,
+    /// The day of the month, from 1 to 31.
+    pub day: u8,
+}
+
+impl Date {
+    /// Creates a date. The parts aren't checked, so it's up to you to pass
+    /// a date that exists.
+    pub fn new(year: i32, month: u8, day: u8) -> Date {
+        Date { year, month, day }
+    }
+}
+
+/// A number to be formatted by a [`Locale`]: either an integer or a
+/// floating-point number.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Number {
+    /// An integer, formatted with [`Locale::format_int`].
+    Int(i128),
+    /// A floating-point number, formatted with [`Locale::format_float`].
+    Float(f64),
+}
+
+macro_rules! impl_number_from {
+    ($variant:ident as $inner:ty: $($ty:ty)*) => {
+        $(
+            impl From<$ty> for Number {
+                fn from(value: $ty) -> Number {
+                    Number::$variant(value as $inner)
+                }
+            }
+        )*
+    };
+}
+
+impl_number_from!(Int as i128: i8 i16 i32 i64 i128 isize u8 u16 u32 u64 usize);
+impl_number_from!(Float as f64: f32 f64);
+
+/// Renders `value`, formatted by `locale`.
+///
+/// See [`Localizer`] for a shorter way to use the same locale throughout a
+/// template.
+///
+/// # Example
+///
+/// ```rust
+/// # use maud::{Date, Locale};
+/// # struct Plain;
+/// # impl Locale for Plain {
+/// #     fn format_int(&self, value: i128) -> String { format!("#{}", value) }
+/// #     fn format_float(&self, value: f64) -> String { value.to_string() }
+/// #     fn format_date(&self, date: Date) -> String { String::new() }
+/// # }
+/// assert_eq!(maud::num(&Plain, 42u8).into_string(), "#42");
+/// ```
+pub fn num(locale: &dyn Locale, value: impl Into<Number>) -> Markup {
+    let text = match value.into() {
+        Number::Int(value) => locale.format_int(value),
+        Number::Float(value) => locale.format_float(value),
+    };
+    let mut buffer = String::with_capacity(text.len());
+    escape::escape_to_string(&text, &mut buffer);
+    PreEscaped(buffer)
+}
+
+/// Renders a `<time>` element for `date`. The content is formatted by
+/// `locale`, and the `datetime` attribute is in `YYYY-MM-DD` form.
+///
+/// See [`Localizer`] for a shorter way to use the same locale throughout a
+/// template.
+pub fn date(locale: &dyn Locale, date: Date) -> Markup {
+    let mut datetime = String::new();
+    let _ = write!(
+        datetime,
+        "{:04}-{:02}-{:02}",
+        date.year, date.month, date.day
+    );
+    time_element(datetime, locale.format_date(date))
+}
+
+/// A [`Locale`] to format values with while rendering a template.
+///
+/// Create one for each request, with the locale that suits the reader, and
+/// pass it to the functions that build the page. The same templates then
+/// render differently depending on the locale.
+#[derive(Clone, Copy)]
+pub struct Localizer<'a> {
+    locale: &'a dyn Locale,
+}
+
+impl<'a> Localizer<'a> {
+    /// Creates a localizer that formats values with `locale`.
+    pub fn new(locale: &'a dyn Locale) -> Localizer<'a> {
+        Localizer { locale }
+    }
+
+    /// Renders `value`, as with [`num`].
+    pub fn num(&self, value: impl Into<Number>) -> Markup {
+        num(self.locale, value)
+    }
+
+    /// Renders a `<time>` element for `date`, as with [`date`](fn@date).
+    pub fn date(&self, value: Date) -> Markup {
+        date(self.locale, value)
+    }
+}
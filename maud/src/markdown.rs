@@ -0,0 +1,148 @@
+use alloc::string::String;
+use pulldown_cmark::{html, CowStr, Event, Options, Parser, Tag};
+
+use crate::Render;
+
+/// Renders `text`, written in CommonMark, as HTML.
+///
+/// As Markdown often comes from users, the output is safe to show by
+/// default: any HTML in the text is escaped rather than passed through, and
+/// links and images only keep their URL if it's relative, or uses the
+/// `http`, `https`, `mailto` or `tel` scheme. Use [`Markdown::options`] to
+/// change this, or to turn on extensions such as tables.
+///
+/// Requires the `markdown` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let body = "Hello, *world*! <script>alert(1)</script>";
+/// let markup = html! {
+///     div.content { (maud::markdown(body)) }
+/// };
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         r#"<div class="content"><p>Hello, <em>world</em>! "#,
+///         "&lt;script&gt;alert(1)&lt;/script&gt;</p>\n</div>",
+///     ),
+/// );
+/// ```
+pub fn markdown(text: &str) -> Markdown<'_> {
+    Markdown {
+        text,
+        options: MarkdownOptions::default(),
+    }
+}
+
+/// Markdown text to render as HTML, as returned by [`markdown`].
+#[derive(Clone, Copy, Debug)]
+pub struct Markdown<'a> {
+    text: &'a str,
+    options: MarkdownOptions,
+}
+
+impl Markdown<'_> {
+    /// Renders the text with `options`, instead of the defaults.
+    pub fn options(mut self, options: MarkdownOptions) -> Self {
+        self.options = options;
+        self
+    }
+}
+
+/// Options for rendering [`markdown`]. The default is plain CommonMark, with
+/// HTML escaped and unsafe URLs removed.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MarkdownOptions {
+    /// Passes HTML in the text through to the output, rather than escaping
+    /// it. Only turn this on for text that you trust.
+    pub raw_html: bool,
+    /// Keeps the URL of every link and image, whatever its scheme, including
+    /// `javascript:`. Only turn this on for text that you trust.
+    pub unsafe_urls: bool,
+    /// Renders tables, as in GitHub Flavored Markdown.
+    pub tables: bool,
+    /// Renders footnotes, written as `[^1]`.
+    pub footnotes: bool,
+    /// Renders `~~text~~` as struck through.
+    pub strikethrough: bool,
+    /// Renders `- [ ]` and `- [x]` list items as checkboxes.
+    pub task_lists: bool,
+    /// Replaces straight quotes, `--` and `...` with their typographic
+    /// forms.
+    pub smart_punctuation: bool,
+}
+
+impl MarkdownOptions {
+    fn extensions(&self) -> Options {
+        let mut options = Options::empty();
+        for (enabled, extension) in [
+            (self.tables, Options::ENABLE_TABLES),
+            (self.footnotes, Options::ENABLE_FOOTNOTES),
+            (self.strikethrough, Options::ENABLE_STRIKETHROUGH),
+            (self.task_lists, Options::ENABLE_TASKLISTS),
+            (self.smart_punctuation, Options::ENABLE_SMART_PUNCTUATION),
+        ] {
+            options.set(extension, enabled);
+        }
+        options
+    }
+}
+
+impl Render for Markdown<'_> {
+    fn render_to(&self, w: &mut String) {
+        let MarkdownOptions {
+            raw_html,
+            unsafe_urls,
+            ..
+        } = self.options;
+        let events =
+            Parser::new_ext(self.text, self.options.extensions()).map(|event| match event {
+                Event::Html(html) | Event::InlineHtml(html) if !raw_html => Event::Text(html),
+                Event::Start(Tag::Link {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }) if !unsafe_urls => Event::Start(Tag::Link {
+                    link_type,
+                    dest_url: safe_url(dest_url),
+                    title,
+                    id,
+                }),
+                Event::Start(Tag::Image {
+                    link_type,
+                    dest_url,
+                    title,
+                    id,
+                }) if !unsafe_urls => Event::Start(Tag::Image {
+                    link_type,
+                    dest_url: safe_url(dest_url),
+                    title,
+                    id,
+                }),
+                event => event,
+            });
+        html::push_html(w, events);
+    }
+}
+
+/// Returns `url` if it's relative or uses a scheme that can't run script,
+/// and an empty URL otherwise.
+fn safe_url(url: CowStr<'_>) -> CowStr<'_> {
+    let scheme = match url.find([':', '/', '?', '#']) {
+        Some(end) if url[end..].starts_with(':') => &url[..end],
+        _ => return url,
+    };
+    if ["http", "https", "mailto", "tel"]
+        .iter()
+        .any(|safe| scheme.eq_ignore_ascii_case(safe))
+    {
+        url
+    } else {
+        CowStr::Borrowed("")
+    }
+}
@@ -0,0 +1,52 @@
+//! Tracking of element nesting depth, enabled with the `debug-nesting`
+//! feature.
+//!
+//! The depth is counted per thread, so that it carries over into templates
+//! rendered by a splice, as in a recursive component.
+
+extern crate std;
+
+use core::cell::Cell;
+
+/// Nesting deeper than this logs a warning.
+pub const WARN_DEPTH: usize = 256;
+
+std::thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    static MAX_DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Called before the body of an element is rendered.
+///
+/// `location` is the `file:line` of the `html!` call that contains the
+/// element.
+pub fn enter_element(location: &'static str) {
+    let depth = DEPTH.with(|depth| {
+        depth.set(depth.get() + 1);
+        depth.get()
+    });
+    MAX_DEPTH.with(|max_depth| max_depth.set(max_depth.get().max(depth)));
+    if depth == WARN_DEPTH + 1 {
+        tracing::warn!(
+            depth,
+            location,
+            "markup is nested more than {} elements deep",
+            WARN_DEPTH
+        );
+    }
+}
+
+/// Called after the body of an element is rendered.
+///
+/// When this closes the outermost element, the deepest nesting reached
+/// since it was opened is logged.
+pub fn exit_element(location: &'static str) {
+    let depth = DEPTH.with(|depth| {
+        depth.set(depth.get().saturating_sub(1));
+        depth.get()
+    });
+    if depth == 0 {
+        let max_depth = MAX_DEPTH.with(|max_depth| max_depth.replace(0));
+        tracing::debug!(max_depth, location, "finished rendering markup");
+    }
+}
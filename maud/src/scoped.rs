@@ -0,0 +1,290 @@
+use alloc::string::String;
+
+use crate::{
+    text::{find_end_tag, tag_len, tag_name},
+    Markup, PreEscaped, Render,
+};
+
+/// Renders `markup` with the stylesheet `css`, rewritten so that it only
+/// applies to that markup.
+///
+/// Each call takes a new scope from `ids`, which is written as an attribute
+/// such as `data-maud-0`. The rewriting works like this:
+///
+/// * Every start tag in the rendered markup gets the scope attribute.
+/// * In every selector of every style rule, the last compound selector (the
+///   part after the last combinator) must also have the attribute. So
+///   `nav > a:hover` becomes `nav > a:hover[data-maud-0]`. If there's a
+///   pseudo-element, such as `::before`, the attribute goes in front of it.
+/// * Rules inside `@media`, `@supports`, `@layer` and `@container` are
+///   rewritten in the same way. Other at-rules, such as `@keyframes` and
+///   `@font-face`, are left alone, as are declarations.
+///
+/// The rewritten stylesheet is placed in a `<style>` element before the
+/// markup. The stylesheet isn't escaped, so it must come from a trusted
+/// source.
+///
+/// Only the last compound selector is scoped, so an ancestor selector may
+/// still match elements outside the markup; `.dark p` styles paragraphs in
+/// the component whenever any ancestor is `.dark`. Nested style rules, using
+/// CSS nesting, aren't rewritten.
+///
+/// Requires the `scoped-css` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, ScopeIds};
+///
+/// let mut ids = ScopeIds::new();
+/// let card = maud::scoped(&mut ids, "p { color: red }", html! { p { "Hi!" } });
+///
+/// assert_eq!(
+///     card.into_string(),
+///     concat!(
+///         "<style>p[data-maud-0] { color: red }</style>",
+///         "<p data-maud-0>Hi!</p>",
+///     ),
+/// );
+/// ```
+pub fn scoped(ids: &mut ScopeIds, css: &str, markup: impl Render) -> Markup {
+    let mut attr = String::from("data-maud-");
+    attr.push_str(itoa::Buffer::new().format(ids.next()));
+    let html = markup.render().0;
+    let mut buffer = String::with_capacity(css.len() + html.len());
+    if !css.trim().is_empty() {
+        buffer.push_str("<style>");
+        scope_css(css, &attr, &mut buffer);
+        buffer.push_str("</style>");
+    }
+    scope_html(&html, &attr, &mut buffer);
+    PreEscaped(buffer)
+}
+
+/// A source of scopes for [`scoped`].
+///
+/// Scopes are numbered from zero, so use one `ScopeIds` for the whole page
+/// to keep them unique.
+#[derive(Debug, Default)]
+pub struct ScopeIds {
+    next: usize,
+}
+
+impl ScopeIds {
+    /// Creates a source that starts from the first scope.
+    pub fn new() -> ScopeIds {
+        ScopeIds::default()
+    }
+
+    fn next(&mut self) -> usize {
+        let id = self.next;
+        self.next += 1;
+        id
+    }
+}
+
+/// Copies `html` into `buffer`, adding `attr` to every start tag.
+fn scope_html(html: &str, attr: &str, buffer: &mut String) {
+    let mut rest = html;
+    while let Some(start) = rest.find('<') {
+        buffer.push_str(&rest[..start]);
+        rest = &rest[start..];
+        if rest.starts_with("<!--") {
+            let end = rest.find("-->").map_or(rest.len(), |end| end + 3);
+            buffer.push_str(&rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        let name = tag_name(&rest[1..]);
+        let len = match tag_len(rest) {
+            Some(len) if name.starts_with(|c: char| c.is_ascii_alphabetic()) => len,
+            // End tags, doctypes, and stray `<`s are copied as they are
+            _ => {
+                buffer.push('<');
+                rest = &rest[1..];
+                continue;
+            }
+        };
+        let tag = &rest[..len];
+        buffer.push('<');
+        buffer.push_str(name);
+        buffer.push(' ');
+        buffer.push_str(attr);
+        buffer.push_str(&tag[1 + name.len()..]);
+        rest = &rest[len..];
+        let name = name.to_ascii_lowercase();
+        if (name == "script" || name == "style") && !tag.ends_with("/>") {
+            // Raw text could contain a `<` that isn't a tag
+            let end = find_end_tag(rest, &name).unwrap_or(rest.len());
+            buffer.push_str(&rest[..end]);
+            rest = &rest[end..];
+        }
+    }
+    buffer.push_str(rest);
+}
+
+/// Copies `css` into `buffer`, requiring `attr` on the subject of every
+/// selector.
+fn scope_css(css: &str, attr: &str, buffer: &mut String) {
+    let mut rest = css;
+    while let Some(i) = find_top_level(rest, b"{;}") {
+        if rest.as_bytes()[i] != b'{' {
+            // A statement at-rule such as `@import`, or a stray `}`
+            buffer.push_str(&rest[..=i]);
+            rest = &rest[i + 1..];
+            continue;
+        }
+        let prelude = &rest[..i];
+        let body = &rest[i + 1..];
+        let Some(len) = block_end(body) else {
+            break;
+        };
+        let body = &body[..len];
+        match at_rule_name(prelude) {
+            Some(name) => {
+                buffer.push_str(prelude);
+                buffer.push('{');
+                if ["media", "supports", "layer", "container"]
+                    .iter()
+                    .any(|group| name.eq_ignore_ascii_case(group))
+                {
+                    scope_css(body, attr, buffer);
+                } else {
+                    buffer.push_str(body);
+                }
+                buffer.push('}');
+            }
+            None => {
+                scope_selectors(prelude, attr, buffer);
+                buffer.push('{');
+                buffer.push_str(body);
+                buffer.push('}');
+            }
+        }
+        rest = &rest[i + 1 + len + 1..];
+    }
+    buffer.push_str(rest);
+}
+
+/// Returns the name of the at-rule that `prelude` starts, if any.
+fn at_rule_name(prelude: &str) -> Option<&str> {
+    let mut prelude = prelude.trim_start();
+    while let Some(comment) = prelude.strip_prefix("/*") {
+        let end = comment.find("*/").map_or(comment.len(), |end| end + 2);
+        prelude = comment[end..].trim_start();
+    }
+    let name = prelude.strip_prefix('@')?;
+    let end = name
+        .find(|c: char| !(c.is_alphanumeric() || c == '-' || c == '_'))
+        .unwrap_or(name.len());
+    Some(&name[..end])
+}
+
+/// Copies a comma-separated list of selectors into `buffer`, scoping each
+/// one.
+fn scope_selectors(selectors: &str, attr: &str, buffer: &mut String) {
+    let mut rest = selectors;
+    while let Some(comma) = find_top_level(rest, b",") {
+        scope_selector(&rest[..comma], attr, buffer);
+        buffer.push(',');
+        rest = &rest[comma + 1..];
+    }
+    scope_selector(rest, attr, buffer);
+}
+
+fn scope_selector(selector: &str, attr: &str, buffer: &mut String) {
+    let trimmed = selector.trim();
+    if trimmed.is_empty() {
+        buffer.push_str(selector);
+        return;
+    }
+    let leading = selector.len() - selector.trim_start().len();
+    let end = leading + trimmed.len();
+    // The last compound selector starts after the last combinator
+    let mut compound = leading;
+    while let Some(i) = find_top_level(&selector[compound..end], b" \t\n\r\x0C>+~") {
+        compound += i + 1;
+    }
+    let insert = compound + pseudo_element(&selector[compound..end]).unwrap_or(end - compound);
+    buffer.push_str(&selector[..insert]);
+    buffer.push('[');
+    buffer.push_str(attr);
+    buffer.push(']');
+    buffer.push_str(&selector[insert..]);
+}
+
+/// Returns the position of the pseudo-element in a compound selector, such
+/// as `::before`, or the older `:before`.
+fn pseudo_element(compound: &str) -> Option<usize> {
+    const LEGACY: [&str; 4] = ["before", "after", "first-line", "first-letter"];
+    let mut from = 0;
+    while let Some(i) = find_top_level(&compound[from..], b":") {
+        let colon = from + i;
+        let name = &compound[colon + 1..];
+        if name.starts_with(':')
+            || LEGACY.iter().any(|legacy| {
+                name.get(..legacy.len())
+                    .is_some_and(|start| start.eq_ignore_ascii_case(legacy))
+                    && !name[legacy.len()..]
+                        .starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '_')
+            })
+        {
+            return Some(colon);
+        }
+        from = colon + 1;
+    }
+    None
+}
+
+/// Returns the position of the `}` that ends a block, given the text after
+/// its `{`.
+fn block_end(css: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut from = 0;
+    loop {
+        let i = from + find_top_level(&css[from..], b"{}")?;
+        if css.as_bytes()[i] == b'{' {
+            depth += 1;
+        } else if depth == 0 {
+            return Some(i);
+        } else {
+            depth -= 1;
+        }
+        from = i + 1;
+    }
+}
+
+/// Returns the position of the first of `targets` in `css` that isn't in a
+/// comment, a string, or a pair of brackets or parentheses.
+///
+/// The targets must be ASCII.
+fn find_top_level(css: &str, targets: &[u8]) -> Option<usize> {
+    let bytes = css.as_bytes();
+    let mut depth = 0usize;
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'"' | b'\'') => {
+                i += 1;
+                while i < bytes.len() && bytes[i] != quote {
+                    if bytes[i] == b'\\' {
+                        i += 1;
+                    }
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i = css[i + 2..]
+                    .find("*/")
+                    .map_or(bytes.len(), |end| i + 2 + end + 1);
+            }
+            b'\\' => i += 1,
+            b'(' | b'[' => depth += 1,
+            b')' | b']' => depth = depth.saturating_sub(1),
+            byte if depth == 0 && targets.contains(&byte) => return Some(i),
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
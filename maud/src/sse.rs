@@ -0,0 +1,39 @@
+use alloc::string::String;
+
+use crate::Render;
+
+/// Renders `markup` as the `data` of a [Server-Sent Event].
+///
+/// Each line of the output is prefixed with `data: `, and the event is
+/// ended with a blank line. `\r\n` and `\r` line endings are treated the
+/// same as `\n`, so the client sees the markup with `\n` line endings.
+///
+/// [Server-Sent Event]: https://html.spec.whatwg.org/multipage/server-sent-events.html
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let frame = maud::sse_data(html! {
+///     li { "New message" }
+///     (maud::PreEscaped("\n"))
+///     li { "Another one" }
+/// });
+///
+/// assert_eq!(
+///     frame,
+///     "data: <li>New message</li>\ndata: <li>Another one</li>\n\n",
+/// );
+/// ```
+pub fn sse_data(markup: impl Render) -> String {
+    let html = markup.render().0.replace("\r\n", "\n");
+    let mut frame = String::with_capacity(html.len() + 8);
+    for line in html.split(['\n', '\r']) {
+        frame.push_str("data: ");
+        frame.push_str(line);
+        frame.push('\n');
+    }
+    frame.push('\n');
+    frame
+}
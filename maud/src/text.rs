@@ -0,0 +1,328 @@
+use alloc::string::String;
+use core::fmt::{Display, Write};
+
+use crate::{escape, Escaper, Markup, PreEscaped, Render};
+
+/// Renders a count followed by the singular or plural form of a word.
+///
+/// The singular form is used if `count` is `1` or `-1`, and the plural form
+/// otherwise. This follows English rules, where zero takes the plural ("0
+/// items"); other languages need a proper localization library.
+///
+/// The output is escaped.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(maud::plural(1, "pony", "ponies").into_string(), "1 pony");
+/// assert_eq!(maud::plural(0, "pony", "ponies").into_string(), "0 ponies");
+/// assert_eq!(maud::plural(6, "pony", "ponies").into_string(), "6 ponies");
+/// ```
+pub fn plural<N>(count: N, singular: &str, plural: &str) -> Markup
+where
+    N: Display + PartialEq + TryFrom<i8>,
+{
+    let word = if is_singular(&count) {
+        singular
+    } else {
+        plural
+    };
+    let mut buffer = String::new();
+    write!(Escaper::new(&mut buffer), "{} {}", count, word).unwrap();
+    PreEscaped(buffer)
+}
+
+/// Like [`plural`], but with the count spliced into a format string.
+///
+/// Each `{}` in the chosen format is replaced with `count`. As with
+/// [`plural`], the singular format is used only for `1` and `-1`.
+///
+/// # Example
+///
+/// ```rust
+/// let markup = maud::plural_fmt(3, "{} cupcake left", "{} cupcakes left");
+/// assert_eq!(markup.into_string(), "3 cupcakes left");
+/// ```
+pub fn plural_fmt<N>(count: N, singular: &str, plural: &str) -> Markup
+where
+    N: Display + PartialEq + TryFrom<i8>,
+{
+    let format = if is_singular(&count) {
+        singular
+    } else {
+        plural
+    };
+    let mut buffer = String::new();
+    let mut escaper = Escaper::new(&mut buffer);
+    for (i, part) in format.split("{}").enumerate() {
+        if i > 0 {
+            write!(escaper, "{}", count).unwrap();
+        }
+        escaper.write_str(part).unwrap();
+    }
+    PreEscaped(buffer)
+}
+
+fn is_singular<N: PartialEq + TryFrom<i8>>(count: &N) -> bool {
+    [1, -1]
+        .into_iter()
+        .filter_map(|one| N::try_from(one).ok())
+        .any(|one| one == *count)
+}
+
+/// Renders `text`, with each occurrence of `term` wrapped in `<mark>`.
+///
+/// Matching is case-insensitive, and the original case of the text is kept.
+/// Matches don't overlap: after a match, the search continues from the end
+/// of it. If `term` is empty, the text is rendered as-is.
+///
+/// Both the text and the term are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(
+///     maud::highlight("Hello World", "wor").into_string(),
+///     "Hello <mark>Wor</mark>ld",
+/// );
+/// ```
+pub fn highlight(text: &str, term: &str) -> Markup {
+    let mut buffer = String::with_capacity(text.len());
+    let mut rest = text;
+    if !term.is_empty() {
+        while let Some((start, end)) = find_ignore_case(rest, term) {
+            escape::escape_to_string(&rest[..start], &mut buffer);
+            buffer.push_str("<mark>");
+            escape::escape_to_string(&rest[start..end], &mut buffer);
+            buffer.push_str("</mark>");
+            rest = &rest[end..];
+        }
+    }
+    escape::escape_to_string(rest, &mut buffer);
+    PreEscaped(buffer)
+}
+
+/// Renders `text` with a `<wbr>` after every `n` characters, so that
+/// browsers can break long words such as URLs or hashes.
+///
+/// Characters are counted as Unicode scalar values, so a multi-byte
+/// character is never split. If `n` is `0`, the text is rendered as-is. No
+/// `<wbr>` is added at the very end.
+///
+/// The text is escaped.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(maud::wbr_every("abcdefgh", 4).into_string(), "abcd<wbr>efgh");
+/// ```
+pub fn wbr_every(text: &str, n: usize) -> Markup {
+    let mut buffer = String::with_capacity(text.len());
+    let mut rest = text;
+    if n > 0 {
+        while let Some((end, _)) = rest.char_indices().nth(n) {
+            escape::escape_to_string(&rest[..end], &mut buffer);
+            buffer.push_str("<wbr>");
+            rest = &rest[end..];
+        }
+    }
+    escape::escape_to_string(rest, &mut buffer);
+    PreEscaped(buffer)
+}
+
+/// Returns the byte range of the first case-insensitive match of `term` in
+/// `text`.
+fn find_ignore_case(text: &str, term: &str) -> Option<(usize, usize)> {
+    text.char_indices().find_map(|(start, _)| {
+        let mut haystack = text[start..].char_indices();
+        for expected in term.chars() {
+            let (_, c) = haystack.next()?;
+            if !c.to_lowercase().eq(expected.to_lowercase()) {
+                return None;
+            }
+        }
+        let end = haystack.next().map_or(text.len(), |(i, _)| start + i);
+        Some((start, end))
+    })
+}
+
+/// Renders `markup`, then removes `separator` from the end, if it's there.
+///
+/// This is for lists built in a loop, where each item is followed by a
+/// separator and the last one is left over. `separator` is text, so it's
+/// escaped before it's compared with the output, and it's left alone if
+/// removing it would cut a character reference such as `&amp;` in half.
+/// Whitespace after the separator is removed along with it; other
+/// whitespace is kept.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let tags = ["rust", "html"];
+/// let markup = html! {
+///     @for tag in &tags { a href={ "/tags/" (tag) } { (tag) } ", " }
+/// };
+///
+/// assert_eq!(
+///     maud::trim_trailing(markup, ",").into_string(),
+///     r#"<a href="/tags/rust">rust</a>, <a href="/tags/html">html</a>"#,
+/// );
+/// ```
+pub fn trim_trailing(markup: impl Render, separator: &str) -> Markup {
+    let mut html = markup.render().0;
+    let mut escaped = String::with_capacity(separator.len());
+    escape::escape_to_string(separator, &mut escaped);
+    let trimmed = html.trim_end();
+    if !escaped.is_empty() && trimmed.ends_with(&escaped) {
+        let start = trimmed.len() - escaped.len();
+        // Don't cut a character reference in half, as `;` would in `&amp;`
+        if !in_reference(&html[..start]) {
+            html.truncate(start);
+        }
+    }
+    PreEscaped(html)
+}
+
+/// Whether `html` ends partway through a character reference, such as
+/// `&amp`.
+fn in_reference(html: &str) -> bool {
+    match html.rfind('&') {
+        Some(i) => html[i + 1..]
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '#'),
+        None => false,
+    }
+}
+
+/// Renders `markup`, then returns its text content with all tags removed and
+/// character references decoded.
+///
+/// This is meant for plain-text previews, such as a `<meta
+/// name="description">` built from the same content as the page. Comments,
+/// and the contents of `<script>` and `<style>` elements, are dropped. Only
+/// the character references that Maud itself produces, plus `&apos;`,
+/// `&nbsp;` and numeric references, are decoded; others are left as-is.
+///
+/// The result is *not* escaped, so it must be escaped again (for example, by
+/// splicing it into `html!`) before going back into HTML.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let markup = html! { p { "Hello " b { "world" } " & goodbye" } };
+/// assert_eq!(maud::strip_tags(&markup), "Hello world & goodbye");
+/// ```
+pub fn strip_tags(markup: impl Render) -> String {
+    let html = markup.render().0;
+    let mut text = String::with_capacity(html.len());
+    let mut rest = html.as_str();
+    while let Some(start) = rest.find(['<', '&']) {
+        text.push_str(&rest[..start]);
+        rest = &rest[start..];
+        rest = if rest.starts_with('&') {
+            decode_reference(rest, &mut text)
+        } else {
+            skip_tag(rest, &mut text)
+        };
+    }
+    text.push_str(rest);
+    text
+}
+
+/// Decodes the character reference at the start of `html` into `text`, and
+/// returns what follows it.
+fn decode_reference<'a>(html: &'a str, text: &mut String) -> &'a str {
+    let decoded = html[1..].find(';').and_then(|end| {
+        let name = &html[1..=end];
+        let c = match name {
+            "amp" => '&',
+            "lt" => '<',
+            "gt" => '>',
+            "quot" => '"',
+            "apos" => '\'',
+            "nbsp" => '\u{A0}',
+            _ => {
+                let code = match name.strip_prefix('#')? {
+                    hex if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16),
+                    decimal => decimal.parse(),
+                };
+                char::from_u32(code.ok()?)?
+            }
+        };
+        Some((c, end + 2))
+    });
+    match decoded {
+        Some((c, len)) => {
+            text.push(c);
+            &html[len..]
+        }
+        None => {
+            text.push('&');
+            &html[1..]
+        }
+    }
+}
+
+/// Skips the tag or comment at the start of `html`, and returns what follows
+/// it. A `<` that doesn't start a tag is copied to `text`.
+fn skip_tag<'a>(html: &'a str, text: &mut String) -> &'a str {
+    if let Some(comment) = html.strip_prefix("<!--") {
+        return comment.find("-->").map_or("", |end| &comment[end + 3..]);
+    }
+    let is_tag = html[1..]
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || matches!(c, '/' | '!' | '?'));
+    if !is_tag {
+        text.push('<');
+        return &html[1..];
+    }
+    let Some(end) = tag_len(html) else {
+        return "";
+    };
+    let name = tag_name(&html[1..end]);
+    let rest = &html[end..];
+    for raw_text in ["script", "style"] {
+        if name.eq_ignore_ascii_case(raw_text) {
+            return find_end_tag(rest, raw_text).map_or("", |close| &rest[close..]);
+        }
+    }
+    rest
+}
+
+/// Returns the length of the tag at the start of `html`, up to and including
+/// its closing `>`. Any `>` in a quoted attribute value is skipped over.
+pub(crate) fn tag_len(html: &str) -> Option<usize> {
+    let mut quote = None;
+    html.char_indices().skip(1).find_map(|(i, c)| {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), _) if q == c => quote = None,
+            (None, '>') => return Some(i + 1),
+            _ => {}
+        }
+        None
+    })
+}
+
+/// Returns the element name at the start of `tag`, which is the text of a
+/// start tag after its `<`.
+pub(crate) fn tag_name(tag: &str) -> &str {
+    let end = tag
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .unwrap_or(tag.len());
+    &tag[..end]
+}
+
+/// Returns the position of the end tag `</name` in `html`, ignoring case.
+pub(crate) fn find_end_tag(html: &str, name: &str) -> Option<usize> {
+    html.match_indices("</").map(|(i, _)| i).find(|&i| {
+        html[i + 2..]
+            .get(..name.len())
+            .is_some_and(|tag| tag.eq_ignore_ascii_case(name))
+    })
+}
@@ -0,0 +1,286 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt;
+
+use crate::{
+    text::{find_end_tag, tag_len, tag_name},
+    Render,
+};
+
+/// Checks that the rendered `markup` is well-formed, and that its elements
+/// are nested in ways that HTML allows.
+///
+/// This is meant for tests, to catch structural mistakes that the `html!`
+/// macro can't see. It's not a full validator; the checks are:
+///
+/// * Every element other than a void element, such as `br` or `img`, has a
+///   matching end tag. Optional end tags must still be written, so the
+///   output of `html_minify!` is not accepted.
+/// * Every end tag matches an open element.
+/// * Block elements, such as `div` or `p`, aren't placed inside a `p` or an
+///   inline element such as `span`.
+///
+/// All problems are reported, in the order they appear in the output.
+///
+/// Requires the `validation` feature.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, ValidationErrorKind};
+///
+/// assert_eq!(maud::validate_html(html! { p { b { "Hi!" } } }), Ok(()));
+///
+/// let errors = maud::validate_html(html! { p { div { "Hi!" } } }).unwrap_err();
+/// assert_eq!(
+///     errors[0].kind,
+///     ValidationErrorKind::InvalidNesting {
+///         child: "div".into(),
+///         parent: "p".into(),
+///     },
+/// );
+/// ```
+pub fn validate_html(markup: impl Render) -> Result<(), Vec<ValidationError>> {
+    let html = markup.render().0;
+    let mut errors = Vec::new();
+    // The open elements, as lowercase names with the offsets of their tags
+    let mut open: Vec<(String, usize)> = Vec::new();
+    let mut offset = 0;
+    while let Some(start) = html[offset..].find('<') {
+        let start = offset + start;
+        let rest = &html[start..];
+        offset = start + 1;
+        if rest.starts_with("<!--") {
+            match rest.find("-->") {
+                Some(end) => offset = start + end + 3,
+                None => {
+                    errors.push(ValidationError::new(
+                        start,
+                        ValidationErrorKind::MalformedTag,
+                    ));
+                    break;
+                }
+            }
+            continue;
+        }
+        let is_end_tag = rest[1..].starts_with('/');
+        let name_start = if is_end_tag { 2 } else { 1 };
+        let name = tag_name(&rest[name_start..]).to_ascii_lowercase();
+        if name.is_empty() || !name.starts_with(|c: char| c.is_ascii_alphabetic()) {
+            // Doctypes, processing instructions, and stray `<`s
+            continue;
+        }
+        let Some(len) = tag_len(rest) else {
+            errors.push(ValidationError::new(
+                start,
+                ValidationErrorKind::MalformedTag,
+            ));
+            break;
+        };
+        offset = start + len;
+        if is_end_tag {
+            close_element(&name, start, &mut open, &mut errors);
+            continue;
+        }
+        if let Some((parent, _)) = open
+            .iter()
+            .rev()
+            .find(|(parent, _)| parent == "p" || is_inline(parent))
+        {
+            if is_block(&name) {
+                errors.push(ValidationError::new(
+                    start,
+                    ValidationErrorKind::InvalidNesting {
+                        child: name.clone(),
+                        parent: parent.clone(),
+                    },
+                ));
+            }
+        }
+        if is_void(&name) || rest[..len].ends_with("/>") {
+            continue;
+        }
+        if name == "script" || name == "style" {
+            // Skip to the end tag, which is then handled as usual
+            match find_end_tag(&html[offset..], &name) {
+                Some(end) => offset += end,
+                None => offset = html.len(),
+            }
+        }
+        open.push((name, start));
+    }
+    for (name, start) in open {
+        errors.push(ValidationError::new(
+            start,
+            ValidationErrorKind::UnclosedElement { name },
+        ));
+    }
+    errors.sort_by_key(|error| error.offset);
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Closes the open element called `name`, reporting any elements inside it
+/// that weren't closed first.
+fn close_element(
+    name: &str,
+    offset: usize,
+    open: &mut Vec<(String, usize)>,
+    errors: &mut Vec<ValidationError>,
+) {
+    match open.iter().rposition(|(other, _)| other == name) {
+        Some(index) => {
+            for (name, start) in open.drain(index..).skip(1) {
+                errors.push(ValidationError::new(
+                    start,
+                    ValidationErrorKind::UnclosedElement { name },
+                ));
+            }
+        }
+        None => errors.push(ValidationError::new(
+            offset,
+            ValidationErrorKind::UnexpectedEndTag { name: name.into() },
+        )),
+    }
+}
+
+fn is_void(name: &str) -> bool {
+    matches!(
+        name,
+        "area"
+            | "base"
+            | "br"
+            | "col"
+            | "embed"
+            | "hr"
+            | "img"
+            | "input"
+            | "link"
+            | "meta"
+            | "source"
+            | "track"
+            | "wbr"
+    )
+}
+
+/// Whether `name` is a block element, which can't go inside a `p`.
+fn is_block(name: &str) -> bool {
+    matches!(
+        name,
+        "address"
+            | "article"
+            | "aside"
+            | "blockquote"
+            | "details"
+            | "dialog"
+            | "div"
+            | "dl"
+            | "fieldset"
+            | "figcaption"
+            | "figure"
+            | "footer"
+            | "form"
+            | "h1"
+            | "h2"
+            | "h3"
+            | "h4"
+            | "h5"
+            | "h6"
+            | "header"
+            | "hgroup"
+            | "hr"
+            | "main"
+            | "menu"
+            | "nav"
+            | "ol"
+            | "p"
+            | "pre"
+            | "search"
+            | "section"
+            | "table"
+            | "ul"
+    )
+}
+
+/// Whether `name` is an inline element, which can only contain text and
+/// other inline elements.
+fn is_inline(name: &str) -> bool {
+    matches!(
+        name,
+        "abbr"
+            | "b"
+            | "bdi"
+            | "bdo"
+            | "cite"
+            | "code"
+            | "data"
+            | "dfn"
+            | "em"
+            | "i"
+            | "kbd"
+            | "label"
+            | "mark"
+            | "q"
+            | "s"
+            | "samp"
+            | "small"
+            | "span"
+            | "strong"
+            | "sub"
+            | "sup"
+            | "time"
+            | "u"
+            | "var"
+    )
+}
+
+/// A problem found by [`validate_html`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The byte offset in the rendered output of the tag with the problem.
+    pub offset: usize,
+    /// What the problem is.
+    pub kind: ValidationErrorKind,
+}
+
+impl ValidationError {
+    fn new(offset: usize, kind: ValidationErrorKind) -> ValidationError {
+        ValidationError { offset, kind }
+    }
+}
+
+/// The kinds of problem found by [`validate_html`].
+///
+/// Element names are in lowercase.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ValidationErrorKind {
+    /// An element has no end tag.
+    UnclosedElement { name: String },
+    /// An end tag doesn't match any open element.
+    UnexpectedEndTag { name: String },
+    /// A block element is inside a `p` or an inline element.
+    InvalidNesting { child: String, parent: String },
+    /// A tag or comment is missing its closing `>` or `-->`.
+    MalformedTag,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.kind {
+            ValidationErrorKind::UnclosedElement { name } => {
+                write!(f, "`<{}>` is never closed", name)?;
+            }
+            ValidationErrorKind::UnexpectedEndTag { name } => {
+                write!(f, "`</{}>` has no matching start tag", name)?;
+            }
+            ValidationErrorKind::InvalidNesting { child, parent } => {
+                write!(f, "`<{}>` can't be placed inside `<{}>`", child, parent)?;
+            }
+            ValidationErrorKind::MalformedTag => f.write_str("unterminated tag")?,
+        }
+        write!(f, " (at byte {})", self.offset)
+    }
+}
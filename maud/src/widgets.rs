@@ -0,0 +1,868 @@
+use alloc::{string::String, vec::Vec};
+use core::fmt::Write;
+
+use crate::{escape, FieldIds, Markup, PreEscaped, Render};
+
+/// Renders a breadcrumb trail, as a `<nav>` containing an ordered list.
+///
+/// Each entry in `crumbs` is a `(label, href)` pair. Every entry but the
+/// last is rendered as a link, followed by `separator`. The last entry is
+/// the current page, so it's rendered as plain text and marked with
+/// `aria-current="page"`. Separators are hidden from screen readers.
+///
+/// Labels and hrefs are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// let crumbs = [("Home", "/"), ("Ponies", "/ponies"), ("Rarity", "/ponies/rarity")];
+///
+/// assert_eq!(
+///     maud::breadcrumbs(&crumbs, "/").into_string(),
+///     concat!(
+///         r#"<nav aria-label="Breadcrumb"><ol>"#,
+///         r#"<li><a href="/">Home</a><span aria-hidden="true">/</span></li>"#,
+///         r#"<li><a href="/ponies">Ponies</a><span aria-hidden="true">/</span></li>"#,
+///         r#"<li aria-current="page">Rarity</li>"#,
+///         "</ol></nav>",
+///     ),
+/// );
+/// ```
+pub fn breadcrumbs<L: AsRef<str>, H: AsRef<str>>(
+    crumbs: &[(L, H)],
+    separator: impl Render,
+) -> Markup {
+    let separator = separator.render();
+    let mut buffer = String::from(r#"<nav aria-label="Breadcrumb"><ol>"#);
+    if let Some(((current, _), links)) = crumbs.split_last() {
+        for (label, href) in links {
+            buffer.push_str("<li><a href=\"");
+            escape::escape_to_string(href.as_ref(), &mut buffer);
+            buffer.push_str("\">");
+            escape::escape_to_string(label.as_ref(), &mut buffer);
+            buffer.push_str("</a><span aria-hidden=\"true\">");
+            buffer.push_str(&separator.0);
+            buffer.push_str("</span></li>");
+        }
+        buffer.push_str(r#"<li aria-current="page">"#);
+        escape::escape_to_string(current.as_ref(), &mut buffer);
+        buffer.push_str("</li>");
+    }
+    buffer.push_str("</ol></nav>");
+    PreEscaped(buffer)
+}
+
+/// Renders a `<meta>` tag for each `(name, content)` pair, for the `<head>`
+/// of a page.
+///
+/// OpenGraph properties, which start with `og:`, `fb:`, `article:`, `book:`,
+/// `music:`, `profile:` or `video:`, are written with a `property`
+/// attribute. Everything else, including `twitter:` names, uses `name`.
+///
+/// Names and contents are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// let tags = [("description", "Cupcakes & more"), ("og:title", "Sugarcube Corner")];
+///
+/// assert_eq!(
+///     maud::meta_tags(&tags).into_string(),
+///     concat!(
+///         r#"<meta name="description" content="Cupcakes &amp; more">"#,
+///         r#"<meta property="og:title" content="Sugarcube Corner">"#,
+///     ),
+/// );
+/// ```
+pub fn meta_tags<N: AsRef<str>, C: AsRef<str>>(tags: &[(N, C)]) -> Markup {
+    const PROPERTY_PREFIXES: [&str; 7] = [
+        "og:", "fb:", "article:", "book:", "music:", "profile:", "video:",
+    ];
+    let mut buffer = String::new();
+    for (name, content) in tags {
+        let name = name.as_ref();
+        let is_property = PROPERTY_PREFIXES.iter().any(|prefix| {
+            name.get(..prefix.len())
+                .is_some_and(|start| start.eq_ignore_ascii_case(prefix))
+        });
+        buffer.push_str(if is_property {
+            "<meta property=\""
+        } else {
+            "<meta name=\""
+        });
+        escape::escape_to_string(name, &mut buffer);
+        buffer.push_str("\" content=\"");
+        escape::escape_to_string(content.as_ref(), &mut buffer);
+        buffer.push_str("\">");
+    }
+    PreEscaped(buffer)
+}
+
+/// Renders the usual tags for the `<head>` of a page, from `meta`.
+///
+/// This is a `<title>`, then a description `<meta>` and a canonical
+/// `<link>`, then OpenGraph and Twitter card tags with the same
+/// information, for link previews. Tags for fields that are `None` are left
+/// out. The Twitter card is `summary_large_image` if there's an image, and
+/// `summary` otherwise.
+///
+/// Everything is escaped.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::PageMeta;
+///
+/// let meta = PageMeta {
+///     title: "Rock farming",
+///     description: Some("All about rocks"),
+///     canonical_url: Some("https://example.com/rocks"),
+///     ..PageMeta::default()
+/// };
+///
+/// assert_eq!(
+///     maud::page_head(&meta).into_string(),
+///     concat!(
+///         "<title>Rock farming</title>",
+///         r#"<meta name="description" content="All about rocks">"#,
+///         r#"<link rel="canonical" href="https://example.com/rocks">"#,
+///         r#"<meta property="og:title" content="Rock farming">"#,
+///         r#"<meta property="og:description" content="All about rocks">"#,
+///         r#"<meta property="og:url" content="https://example.com/rocks">"#,
+///         r#"<meta name="twitter:card" content="summary">"#,
+///     ),
+/// );
+/// ```
+pub fn page_head(meta: &PageMeta) -> Markup {
+    let mut buffer = String::from("<title>");
+    escape::escape_to_string(meta.title, &mut buffer);
+    buffer.push_str("</title>");
+    if let Some(description) = meta.description {
+        buffer.push_str(&meta_tags(&[("description", description)]).0);
+    }
+    if let Some(canonical_url) = meta.canonical_url {
+        buffer.push_str("<link rel=\"canonical\" href=\"");
+        escape::escape_to_string(canonical_url, &mut buffer);
+        buffer.push_str("\">");
+    }
+    let mut tags = alloc::vec![("og:title", meta.title)];
+    for (name, value) in [
+        ("og:description", meta.description),
+        ("og:url", meta.canonical_url),
+        ("og:image", meta.og_image),
+        ("og:site_name", meta.site_name),
+    ] {
+        if let Some(value) = value {
+            tags.push((name, value));
+        }
+    }
+    tags.push((
+        "twitter:card",
+        if meta.og_image.is_some() {
+            "summary_large_image"
+        } else {
+            "summary"
+        },
+    ));
+    buffer.push_str(&meta_tags(&tags).0);
+    PreEscaped(buffer)
+}
+
+/// The metadata of a page, for [`page_head`].
+#[derive(Clone, Debug, Default)]
+pub struct PageMeta<'a> {
+    /// The title of the page.
+    pub title: &'a str,
+    /// A summary of the page, for search results and link previews.
+    pub description: Option<&'a str>,
+    /// The preferred URL of the page, when the same content is at more than
+    /// one URL.
+    pub canonical_url: Option<&'a str>,
+    /// The URL of an image for link previews.
+    pub og_image: Option<&'a str>,
+    /// The name of the whole site, for link previews.
+    pub site_name: Option<&'a str>,
+}
+
+/// Renders a `<link rel="preload">` tag for each `(url, as_type)` pair, for
+/// the `<head>` of a page.
+///
+/// `as_type` must be `script`, `style`, `font` or `image`, ignoring ASCII
+/// case; an asset of any other type is left out, as the browser would
+/// ignore it anyway. Fonts are always fetched in CORS mode, so their links
+/// get a `crossorigin` attribute.
+///
+/// URLs are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// let assets = [("/app.js", "script"), ("/inter.woff2", "font")];
+///
+/// assert_eq!(
+///     maud::preload_links(&assets).into_string(),
+///     concat!(
+///         r#"<link rel="preload" href="/app.js" as="script">"#,
+///         r#"<link rel="preload" href="/inter.woff2" as="font" crossorigin>"#,
+///     ),
+/// );
+/// ```
+pub fn preload_links<U: AsRef<str>, T: AsRef<str>>(assets: &[(U, T)]) -> Markup {
+    const TYPES: [&str; 4] = ["script", "style", "font", "image"];
+    let mut buffer = String::new();
+    for (url, as_type) in assets {
+        let Some(as_type) = TYPES
+            .iter()
+            .find(|allowed| as_type.as_ref().trim().eq_ignore_ascii_case(allowed))
+        else {
+            continue;
+        };
+        buffer.push_str("<link rel=\"preload\" href=\"");
+        escape::escape_to_string(url.as_ref(), &mut buffer);
+        buffer.push_str("\" as=\"");
+        buffer.push_str(as_type);
+        buffer.push('"');
+        if *as_type == "font" {
+            buffer.push_str(" crossorigin");
+        }
+        buffer.push('>');
+    }
+    PreEscaped(buffer)
+}
+
+/// Renders a `<meta http-equiv="refresh">` tag, which sends the browser to
+/// `url` after `seconds` seconds.
+///
+/// The URL is quoted within the `content` value, so a `;` or `,` in it is
+/// kept. Quote characters in the URL are percent-encoded, so they can't end
+/// it early, and the whole value is escaped.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(
+///     maud::meta_refresh(5, "/login?next=/a&b").into_string(),
+///     r#"<meta http-equiv="refresh" content="5; url='/login?next=/a&amp;b'">"#,
+/// );
+/// ```
+pub fn meta_refresh(seconds: u32, url: &str) -> Markup {
+    let mut content = String::new();
+    write!(content, "{}; url='", seconds).unwrap();
+    for c in url.trim().chars() {
+        match c {
+            '\'' => content.push_str("%27"),
+            '"' => content.push_str("%22"),
+            c => content.push(c),
+        }
+    }
+    content.push('\'');
+    let mut buffer = String::from("<meta http-equiv=\"refresh\" content=\"");
+    escape::escape_to_string(&content, &mut buffer);
+    buffer.push_str("\">");
+    PreEscaped(buffer)
+}
+
+/// Renders pagination controls: links to the previous and next pages, and
+/// to a window of page numbers around the current page.
+///
+/// Pages are numbered from 1 to `total`, and `url` returns the link for a
+/// given page. The current page is marked with `aria-current="page"`. On the
+/// first and last pages, the previous and next links are disabled. If
+/// there's only one page, nothing is rendered.
+///
+/// By default, two pages either side of the current one are shown; use
+/// [`.window()`](Pagination::window) to change this.
+///
+/// # Example
+///
+/// ```rust
+/// let markup = maud::pagination(1, 3, |page| format!("/posts?page={}", page));
+///
+/// assert_eq!(
+///     maud::Render::render(&markup).into_string(),
+///     concat!(
+///         r#"<nav aria-label="Pagination"><ul>"#,
+///         r#"<li><span aria-disabled="true">Previous</span></li>"#,
+///         r#"<li><a href="/posts?page=1" aria-current="page">1</a></li>"#,
+///         r#"<li><a href="/posts?page=2">2</a></li>"#,
+///         r#"<li><a href="/posts?page=3">3</a></li>"#,
+///         r#"<li><a href="/posts?page=2" rel="next">Next</a></li>"#,
+///         "</ul></nav>",
+///     ),
+/// );
+/// ```
+pub fn pagination<F: Fn(usize) -> String>(current: usize, total: usize, url: F) -> Pagination<F> {
+    Pagination {
+        current,
+        total,
+        url,
+        window: 2,
+    }
+}
+
+/// Pagination controls, as returned by [`pagination`].
+pub struct Pagination<F> {
+    current: usize,
+    total: usize,
+    url: F,
+    window: usize,
+}
+
+impl<F> Pagination<F> {
+    /// Sets how many page numbers are shown on each side of the current
+    /// page.
+    pub fn window(mut self, window: usize) -> Self {
+        self.window = window;
+        self
+    }
+}
+
+impl<F: Fn(usize) -> String> Pagination<F> {
+    fn link(&self, w: &mut String, page: usize, attrs: &str, label: &str) {
+        w.push_str("<li><a href=\"");
+        escape::escape_to_string(&(self.url)(page), w);
+        w.push('"');
+        w.push_str(attrs);
+        w.push('>');
+        w.push_str(label);
+        w.push_str("</a></li>");
+    }
+
+    fn disabled(&self, w: &mut String, label: &str) {
+        w.push_str(r#"<li><span aria-disabled="true">"#);
+        w.push_str(label);
+        w.push_str("</span></li>");
+    }
+}
+
+impl<F: Fn(usize) -> String> Render for Pagination<F> {
+    fn render_to(&self, w: &mut String) {
+        if self.total <= 1 {
+            return;
+        }
+        let current = self.current.clamp(1, self.total);
+        w.push_str(r#"<nav aria-label="Pagination"><ul>"#);
+        if current > 1 {
+            self.link(w, current - 1, r#" rel="prev""#, "Previous");
+        } else {
+            self.disabled(w, "Previous");
+        }
+        let first = current.saturating_sub(self.window).max(1);
+        let last = current.saturating_add(self.window).min(self.total);
+        for page in first..=last {
+            let mut label = itoa::Buffer::new();
+            let label = label.format(page);
+            if page == current {
+                self.link(w, page, r#" aria-current="page""#, label);
+            } else {
+                self.link(w, page, "", label);
+            }
+        }
+        if current < self.total {
+            self.link(w, current + 1, r#" rel="next""#, "Next");
+        } else {
+            self.disabled(w, "Next");
+        }
+        w.push_str("</ul></nav>");
+    }
+}
+
+/// Renders a `<progress>` element showing how far `value` is towards `max`.
+///
+/// The value is clamped to lie between zero and `max`, and is mirrored in
+/// `aria-valuenow`, `aria-valuemin` and `aria-valuemax`. If `max` isn't a
+/// positive number, or `value` is NaN, the progress bar is rendered as
+/// indeterminate, with no value at all.
+///
+/// Use [`.fallback()`](Progress::fallback) to also show the percentage as
+/// text, for browsers that don't support `<progress>`.
+///
+/// # Example
+///
+/// ```rust
+/// let markup = maud::progress(3, 4).fallback();
+///
+/// assert_eq!(
+///     maud::Render::render(&markup).into_string(),
+///     concat!(
+///         r#"<progress value="3" max="4" "#,
+///         r#"aria-valuenow="3" aria-valuemin="0" aria-valuemax="4">"#,
+///         "75%</progress>",
+///     ),
+/// );
+/// ```
+pub fn progress(value: impl Into<f64>, max: impl Into<f64>) -> Progress {
+    Progress {
+        value: value.into(),
+        max: max.into(),
+        fallback: false,
+    }
+}
+
+/// A progress bar, as returned by [`progress`].
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    value: f64,
+    max: f64,
+    fallback: bool,
+}
+
+impl Progress {
+    /// Shows the percentage complete, rounded to the nearest whole number,
+    /// inside the element.
+    pub fn fallback(mut self) -> Self {
+        self.fallback = true;
+        self
+    }
+}
+
+impl Render for Progress {
+    fn render_to(&self, w: &mut String) {
+        // Written this way round so that NaN counts as indeterminate
+        if !(self.max > 0.0 && self.max.is_finite()) || self.value.is_nan() {
+            w.push_str("<progress></progress>");
+            return;
+        }
+        let value = self.value.clamp(0.0, self.max);
+        let _ = write!(
+            w,
+            r#"<progress value="{value}" max="{max}" aria-valuenow="{value}" aria-valuemin="0" aria-valuemax="{max}">"#,
+            value = value,
+            max = self.max,
+        );
+        if self.fallback {
+            // Adding a half then truncating rounds, since the ratio is never
+            // negative
+            let percent = (value / self.max * 100.0 + 0.5) as u32;
+            w.push_str(itoa::Buffer::new().format(percent));
+            w.push('%');
+        }
+        w.push_str("</progress>");
+    }
+}
+
+/// Renders a keyboard shortcut, with each key in its own `<kbd>` element.
+///
+/// The keys are joined with `+`; use [`.separator()`](KbdCombo::separator)
+/// to change it. Key names are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// let markup = maud::kbd_combo(&["Ctrl", "Shift", "P"]);
+///
+/// assert_eq!(
+///     maud::Render::render(&markup).into_string(),
+///     "<kbd>Ctrl</kbd>+<kbd>Shift</kbd>+<kbd>P</kbd>",
+/// );
+/// ```
+pub fn kbd_combo<K: AsRef<str>>(keys: &[K]) -> KbdCombo<'_, K> {
+    KbdCombo {
+        keys,
+        separator: PreEscaped("+".into()),
+    }
+}
+
+/// A keyboard shortcut, as returned by [`kbd_combo`].
+#[derive(Clone, Debug)]
+pub struct KbdCombo<'a, K> {
+    keys: &'a [K],
+    separator: Markup,
+}
+
+impl<K> KbdCombo<'_, K> {
+    /// Places `separator` between the keys, instead of `+`.
+    pub fn separator(mut self, separator: impl Render) -> Self {
+        self.separator = separator.render();
+        self
+    }
+}
+
+impl<K: AsRef<str>> Render for KbdCombo<'_, K> {
+    fn render_to(&self, w: &mut String) {
+        for (i, key) in self.keys.iter().enumerate() {
+            if i > 0 {
+                w.push_str(&self.separator.0);
+            }
+            w.push_str("<kbd>");
+            escape::escape_to_string(key.as_ref(), w);
+            w.push_str("</kbd>");
+        }
+    }
+}
+
+/// Renders a "Skip to main content" link, which lets keyboard users jump
+/// past the navigation to `target`.
+///
+/// Place it first in the `<body>`. `target` is the ID of the main content,
+/// with or without a leading `#`, and is escaped. Use
+/// [`.text()`](SkipLink::text) to change the link text.
+///
+/// The link has the class `maud-skip-link`, which should hide it until it
+/// has focus. This CSS is enough:
+///
+/// ```css
+/// .maud-skip-link:not(:focus) {
+///   position: absolute;
+///   width: 1px;
+///   height: 1px;
+///   overflow: hidden;
+///   clip-path: inset(50%);
+///   white-space: nowrap;
+/// }
+/// ```
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let markup = html! {
+///     (maud::skip_link("#main"))
+///     nav { "..." }
+///     main #main { "..." }
+/// };
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         r##"<a class="maud-skip-link" href="#main">Skip to main content</a>"##,
+///         r#"<nav>...</nav><main id="main">...</main>"#,
+///     ),
+/// );
+/// ```
+pub fn skip_link(target: &str) -> SkipLink<'_> {
+    SkipLink {
+        target: target.strip_prefix('#').unwrap_or(target),
+        text: PreEscaped("Skip to main content".into()),
+    }
+}
+
+/// A link to skip to the main content, as returned by [`skip_link`].
+#[derive(Clone, Debug)]
+pub struct SkipLink<'a> {
+    target: &'a str,
+    text: Markup,
+}
+
+impl SkipLink<'_> {
+    /// Uses `text` as the link text, instead of "Skip to main content".
+    pub fn text(mut self, text: impl Render) -> Self {
+        self.text = text.render();
+        self
+    }
+}
+
+impl Render for SkipLink<'_> {
+    fn render_to(&self, w: &mut String) {
+        w.push_str("<a class=\"maud-skip-link\" href=\"#");
+        escape::escape_to_string(self.target, w);
+        w.push_str("\">");
+        w.push_str(&self.text.0);
+        w.push_str("</a>");
+    }
+}
+
+/// Renders `scripted`, followed by `fallback` inside a `<noscript>`
+/// element, for browsers with scripting turned off.
+///
+/// This keeps a progressively enhanced widget next to its fallback.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let markup = maud::with_noscript(
+///     html! { div #map {} },
+///     html! { a href="/map.png" { "View the map" } },
+/// );
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         r#"<div id="map"></div>"#,
+///         r#"<noscript><a href="/map.png">View the map</a></noscript>"#,
+///     ),
+/// );
+/// ```
+pub fn with_noscript(scripted: impl Render, fallback: impl Render) -> Markup {
+    let mut buffer = String::new();
+    scripted.render_to(&mut buffer);
+    buffer.push_str("<noscript>");
+    fallback.render_to(&mut buffer);
+    buffer.push_str("</noscript>");
+    PreEscaped(buffer)
+}
+
+/// Renders a user's avatar: an `<img>` of `image_url` if there is one, or
+/// else a placeholder with the initials of `name`.
+///
+/// The initials are the first letters of the first and last words of
+/// `name`, in uppercase. The placeholder is a `<div>` with `role="img"`,
+/// labelled with the full name. The URL and name are escaped.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(
+///     maud::avatar(None, "Jane Doe").into_string(),
+///     r#"<div role="img" aria-label="Jane Doe">JD</div>"#,
+/// );
+/// assert_eq!(
+///     maud::avatar(Some("/jane.png"), "Jane Doe").into_string(),
+///     r#"<img src="/jane.png" alt="Jane Doe">"#,
+/// );
+/// ```
+pub fn avatar(image_url: Option<&str>, name: &str) -> Markup {
+    let mut buffer = String::new();
+    match image_url {
+        Some(url) => {
+            buffer.push_str("<img src=\"");
+            escape::escape_to_string(url, &mut buffer);
+            buffer.push_str("\" alt=\"");
+            escape::escape_to_string(name, &mut buffer);
+            buffer.push_str("\">");
+        }
+        None => {
+            buffer.push_str("<div role=\"img\" aria-label=\"");
+            escape::escape_to_string(name, &mut buffer);
+            buffer.push_str("\">");
+            escape::escape_to_string(&initials(name), &mut buffer);
+            buffer.push_str("</div>");
+        }
+    }
+    PreEscaped(buffer)
+}
+
+/// The uppercase first letters of the first and last words of `name`.
+fn initials(name: &str) -> String {
+    let mut words = name.split_whitespace();
+    let first = words.next();
+    let last = words.next_back();
+    first
+        .into_iter()
+        .chain(last)
+        .filter_map(|word| word.chars().next())
+        .flat_map(char::to_uppercase)
+        .collect()
+}
+
+/// Renders a disclosure widget: a `<details>` element with `summary` in its
+/// `<summary>`, followed by `content`, which is hidden until it's opened.
+///
+/// If `open` is true, the widget starts open.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::html;
+///
+/// let markup = maud::details("Ingredients", html! { p { "Flour & sugar" } }, true);
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         "<details open><summary>Ingredients</summary>",
+///         "<p>Flour &amp; sugar</p></details>",
+///     ),
+/// );
+/// ```
+pub fn details(summary: impl Render, content: impl Render, open: bool) -> Markup {
+    let mut buffer = String::from(if open { "<details open>" } else { "<details>" });
+    buffer.push_str("<summary>");
+    summary.render_to(&mut buffer);
+    buffer.push_str("</summary>");
+    content.render_to(&mut buffer);
+    buffer.push_str("</details>");
+    PreEscaped(buffer)
+}
+
+/// Renders a set of tabs: a `tablist` of buttons, followed by a `tabpanel`
+/// for each `(label, content)` pair.
+///
+/// Each tab takes an ID from `ids`, such as `page-0`, and its button and
+/// panel get IDs based on it, such as `page-0-tab` and `page-0-panel`.
+/// These link each button to its panel with `aria-controls`, and each panel
+/// to its button with `aria-labelledby`.
+///
+/// The first tab is selected. The other buttons have `tabindex="-1"`, so
+/// that only the selected tab is in the tab order, and their panels are
+/// `hidden`. Switching tabs needs a script, which isn't included.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, FieldIds};
+///
+/// let mut ids = FieldIds::new("pony");
+/// let markup = maud::tabs(&mut ids, &[("Bio", html! { p { "Hi!" } }), ("Art", html! {})]);
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     concat!(
+///         r#"<div role="tablist">"#,
+///         r#"<button type="button" role="tab" id="pony-0-tab" aria-controls="pony-0-panel" aria-selected="true">Bio</button>"#,
+///         r#"<button type="button" role="tab" id="pony-1-tab" aria-controls="pony-1-panel" aria-selected="false" tabindex="-1">Art</button>"#,
+///         "</div>",
+///         r#"<div role="tabpanel" id="pony-0-panel" aria-labelledby="pony-0-tab" tabindex="0"><p>Hi!</p></div>"#,
+///         r#"<div role="tabpanel" id="pony-1-panel" aria-labelledby="pony-1-tab" tabindex="0" hidden></div>"#,
+///     ),
+/// );
+/// ```
+pub fn tabs<L: Render, C: Render>(ids: &mut FieldIds, tabs: &[(L, C)]) -> Markup {
+    let ids = tabs.iter().map(|_| ids.next()).collect::<Vec<_>>();
+    let mut buffer = String::from("<div role=\"tablist\">");
+    for (i, ((label, _), id)) in tabs.iter().zip(&ids).enumerate() {
+        buffer.push_str("<button type=\"button\" role=\"tab\" id=\"");
+        escape::escape_to_string(id, &mut buffer);
+        buffer.push_str("-tab\" aria-controls=\"");
+        escape::escape_to_string(id, &mut buffer);
+        buffer.push_str(if i == 0 {
+            "-panel\" aria-selected=\"true\">"
+        } else {
+            "-panel\" aria-selected=\"false\" tabindex=\"-1\">"
+        });
+        label.render_to(&mut buffer);
+        buffer.push_str("</button>");
+    }
+    buffer.push_str("</div>");
+    for (i, ((_, content), id)) in tabs.iter().zip(&ids).enumerate() {
+        buffer.push_str("<div role=\"tabpanel\" id=\"");
+        escape::escape_to_string(id, &mut buffer);
+        buffer.push_str("-panel\" aria-labelledby=\"");
+        escape::escape_to_string(id, &mut buffer);
+        buffer.push_str(if i == 0 {
+            "-tab\" tabindex=\"0\">"
+        } else {
+            "-tab\" tabindex=\"0\" hidden>"
+        });
+        content.render_to(&mut buffer);
+        buffer.push_str("</div>");
+    }
+    PreEscaped(buffer)
+}
+
+/// Renders `content` in a live region, which screen readers announce
+/// whenever it changes.
+///
+/// The region is a `<div>` with `aria-live` set to `politeness`, and
+/// `aria-atomic="true"` so the whole region is read out rather than just
+/// the part that changed.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::{html, Politeness};
+///
+/// let markup = maud::live_region(Politeness::Polite, html! { "Saved." });
+///
+/// assert_eq!(
+///     markup.into_string(),
+///     r#"<div aria-live="polite" aria-atomic="true">Saved.</div>"#,
+/// );
+/// ```
+pub fn live_region(politeness: Politeness, content: impl Render) -> Markup {
+    let mut buffer = String::from("<div aria-live=\"");
+    buffer.push_str(politeness.as_str());
+    buffer.push_str("\" aria-atomic=\"true\">");
+    content.render_to(&mut buffer);
+    buffer.push_str("</div>");
+    PreEscaped(buffer)
+}
+
+/// How urgently a [`live_region`] is announced.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Politeness {
+    /// Announced when the user is idle.
+    #[default]
+    Polite,
+    /// Announced straight away, interrupting the user.
+    Assertive,
+    /// Not announced, unless the region has focus.
+    Off,
+}
+
+impl Politeness {
+    /// Reads a politeness from its `aria-live` value, ignoring ASCII case.
+    ///
+    /// Anything other than `polite`, `assertive` or `off` is taken as
+    /// `polite`, so that a bad value still gets announced.
+    pub fn from_str_lossy(value: &str) -> Politeness {
+        let value = value.trim();
+        if value.eq_ignore_ascii_case("assertive") {
+            Politeness::Assertive
+        } else if value.eq_ignore_ascii_case("off") {
+            Politeness::Off
+        } else {
+            Politeness::Polite
+        }
+    }
+
+    /// The `aria-live` value.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Politeness::Polite => "polite",
+            Politeness::Assertive => "assertive",
+            Politeness::Off => "off",
+        }
+    }
+}
+
+/// Renders an icon from an SVG sprite on the current page.
+///
+/// This is the same as [`IconSet::icon`], for sprites that are inlined in
+/// the page rather than loaded from a separate file.
+///
+/// # Example
+///
+/// ```rust
+/// assert_eq!(
+///     maud::icon("home").into_string(),
+///     r##"<svg><use href="#home"></use></svg>"##,
+/// );
+/// ```
+pub fn icon(name: &str) -> Markup {
+    IconSet::new("").icon(name)
+}
+
+/// A set of icons stored in an SVG sprite file.
+///
+/// # Example
+///
+/// ```rust
+/// use maud::IconSet;
+///
+/// let icons = IconSet::new("/static/icons.svg");
+///
+/// assert_eq!(
+///     icons.icon("home").into_string(),
+///     r##"<svg><use href="/static/icons.svg#home"></use></svg>"##,
+/// );
+/// ```
+#[derive(Clone, Debug)]
+pub struct IconSet {
+    sprite: String,
+}
+
+impl IconSet {
+    /// Creates an icon set backed by the sprite at the given URL.
+    pub fn new(sprite: impl Into<String>) -> IconSet {
+        IconSet {
+            sprite: sprite.into(),
+        }
+    }
+
+    /// Renders the named icon, as an `<svg>` element that refers to the
+    /// `<symbol>` with that ID in the sprite.
+    ///
+    /// The sprite URL and icon name are escaped.
+    pub fn icon(&self, name: &str) -> Markup {
+        let mut buffer = String::from("<svg><use href=\"");
+        escape::escape_to_string(&self.sprite, &mut buffer);
+        buffer.push('#');
+        escape::escape_to_string(name, &mut buffer);
+        buffer.push_str("\"></use></svg>");
+        PreEscaped(buffer)
+    }
+}
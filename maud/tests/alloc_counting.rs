@@ -0,0 +1,21 @@
+#![cfg(feature = "alloc-counting")]
+
+use maud::html;
+
+#[test]
+fn output_is_unchanged() {
+    let rows = (0..100).map(|i| format!("row {}", i)).collect::<Vec<_>>();
+    let result = html! {
+        @define cell(text: &str) { td { (text) } }
+        table {
+            @for row in &rows {
+                tr { @use cell(row) }
+            }
+        }
+    };
+    let expected = rows
+        .iter()
+        .map(|row| format!("<tr><td>{}</td></tr>", row))
+        .collect::<String>();
+    assert_eq!(result.into_string(), format!("<table>{}</table>", expected));
+}
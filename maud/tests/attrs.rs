@@ -0,0 +1,173 @@
+use maud::{cx, html, Attrs};
+
+#[test]
+fn cx_merges_and_normalizes() {
+    assert_eq!(
+        cx(" card \n shadow  ", [("active", true), ("hidden", false)]),
+        "card shadow active"
+    );
+    assert_eq!(cx("", [("a b", true), ("c", true)]), "a b c");
+    assert_eq!(cx("   ", []), "");
+}
+
+#[test]
+fn cx_dedups() {
+    assert_eq!(
+        cx("btn btn", [("btn", true), ("big", true), ("big", true)]),
+        "btn big"
+    );
+}
+
+#[test]
+fn cx_in_attribute() {
+    let result = html! { div class=(cx("x", [("\"quoted\"", true)])) {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<div class="x &quot;quoted&quot;"></div>"#
+    );
+}
+
+#[test]
+fn enum_class_kebab_case() {
+    use maud::enum_class;
+    assert_eq!(enum_class("status", "Active"), "status status-active");
+    assert_eq!(
+        enum_class("status", "InProgress"),
+        "status status-in-progress"
+    );
+    assert_eq!(
+        enum_class("status", "in progress"),
+        "status status-in-progress"
+    );
+    assert_eq!(enum_class("status", "ON_HOLD"), "status status-on-hold");
+    assert_eq!(enum_class("err", "HTTPError"), "err err-http-error");
+    assert_eq!(enum_class("lvl", "Level2Boss"), "lvl lvl-level2-boss");
+    assert_eq!(enum_class("x", "--Ünïcode Ärger!--"), "x x-ünïcode-ärger");
+    assert_eq!(enum_class("x", 42), "x x-42");
+}
+
+#[test]
+fn enum_class_edge_cases() {
+    use maud::enum_class;
+    assert_eq!(enum_class(" status ", "!!"), "status");
+    assert_eq!(enum_class("", "Active"), "active");
+    assert_eq!(enum_class("", ""), "");
+}
+
+#[test]
+fn enum_class_in_attribute() {
+    let result = html! { p class=(maud::enum_class("a<b", "C\"D")) {} };
+    assert_eq!(result.into_string(), r#"<p class="a&lt;b a&lt;b-c-d"></p>"#);
+}
+
+#[test]
+fn srcset_candidates() {
+    use maud::srcset;
+    assert_eq!(
+        srcset(&[("a.png", "480w"), ("b.png", " 800w "), ("c.png", "")]),
+        "a.png 480w, b.png 800w, c.png"
+    );
+    assert_eq!(
+        srcset(&[(",my pony,.png".to_owned(), "1x".to_owned())]),
+        "%2Cmy%20pony,.png 1x"
+    );
+    let empty: &[(&str, &str)] = &[];
+    assert_eq!(srcset(empty), "");
+}
+
+#[test]
+fn srcset_in_attribute() {
+    let variants = [("/a.png?w=1&h=2", "1x"), ("/\"b\".png", "2x")];
+    let result = html! { img srcset=(maud::srcset(&variants)); };
+    assert_eq!(
+        result.into_string(),
+        r#"<img srcset="/a.png?w=1&amp;h=2 1x, /&quot;b&quot;.png 2x">"#
+    );
+}
+
+#[test]
+fn attrs_builder() {
+    let attrs = Attrs::new()
+        .set("class", "card")
+        .toggle("hidden", false)
+        .toggle("data-active", true)
+        .optional("href", Some("/a?b=1&c=2"))
+        .optional("title", None::<&str>)
+        .set("data-count", 3);
+    let result = html! { a id="x" (attrs) {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<a id="x" class="card" data-active href="/a?b=1&amp;c=2" data-count="3"></a>"#
+    );
+}
+
+#[test]
+fn attrs_builder_escapes_values() {
+    let attrs = Attrs::new().set("title", "\"><script>");
+    let result = html! { p (&attrs) {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<p title="&quot;&gt;&lt;script&gt;"></p>"#
+    );
+}
+
+#[test]
+fn attrs_builder_later_calls_win() {
+    let attrs = Attrs::new()
+        .set("class", "a")
+        .set("id", "b")
+        .set("class", "c")
+        .toggle("id", false)
+        .optional("lang", Some("en"))
+        .optional("lang", None::<&str>);
+    let result = html! { p (attrs) {} };
+    assert_eq!(result.into_string(), r#"<p class="c"></p>"#);
+}
+
+#[test]
+fn attrs_builder_empty() {
+    let attrs = Attrs::new().toggle("hidden", false);
+    assert!(attrs.is_empty());
+    let result = html! { br (attrs); };
+    assert_eq!(result.into_string(), "<br>");
+}
+
+#[test]
+#[should_panic(expected = "invalid attribute name")]
+fn attrs_builder_rejects_invalid_names() {
+    let _ = Attrs::new().set("onclick=alert(1) x", "");
+}
+
+#[test]
+fn hx_attributes() {
+    let result = html! {
+        form #search (maud::hx().post("/search?q=a&b").trigger("keyup changed delay:500ms").target("#results")) {}
+    };
+    assert_eq!(
+        result.into_string(),
+        r##"<form id="search" hx-post="/search?q=a&amp;b" hx-trigger="keyup changed delay:500ms" hx-target="#results"></form>"##
+    );
+}
+
+#[test]
+fn hx_escapes_and_replaces() {
+    let hx = maud::hx()
+        .confirm("Delete \"Rarity\"?")
+        .delete("/ponies/1")
+        .confirm("Really delete \"Rarity\"?");
+    let result = html! { button (&hx) { "Delete" } };
+    assert_eq!(
+        result.into_string(),
+        r#"<button hx-confirm="Really delete &quot;Rarity&quot;?" hx-delete="/ponies/1">Delete</button>"#
+    );
+}
+
+#[test]
+fn hx_into_attrs() {
+    let attrs = maud::hx().get("/more").into_attrs().set("class", "btn");
+    let result = html! { button (attrs) {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<button hx-get="/more" class="btn"></button>"#
+    );
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "audit")]
+
+use maud::html;
+
+#[test]
+fn splices_are_marked() {
+    maud::set_audit(true);
+    let name = "<Pinkie>";
+    let line = line!() + 2;
+    let result = html! {
+        p title=(name) { (name) ("static") }
+        script { (name) }
+        title { (name) }
+        textarea { (name) }
+    };
+    assert_eq!(
+        result.into_string(),
+        format!(
+            concat!(
+                r#"<p title="&lt;Pinkie&gt;"><!-- splice: {}:{} -->&lt;Pinkie&gt;static</p>"#,
+                "<script>&lt;Pinkie&gt;</script>",
+                "<title>&lt;Pinkie&gt;</title>",
+                "<textarea>&lt;Pinkie&gt;</textarea>",
+            ),
+            file!(),
+            line,
+        )
+    );
+}
+
+#[test]
+fn each_splice_has_its_own_line() {
+    maud::set_audit(true);
+    let line = line!() + 2;
+    let result = html! {
+        (1)
+        (2)
+    };
+    assert_eq!(
+        result.into_string(),
+        format!(
+            "<!-- splice: {file}:{} -->1<!-- splice: {file}:{} -->2",
+            line,
+            line + 1,
+            file = file!(),
+        )
+    );
+}
@@ -1,4 +1,4 @@
-use maud::{html, Markup};
+use maud::{html, Markup, PreEscaped};
 
 #[test]
 fn literals() {
@@ -106,6 +106,29 @@ fn toggle_empty_attributes_braces() {
     assert_eq!(result.into_string(), "<input checked>");
 }
 
+#[test]
+fn toggle_empty_attributes_options() {
+    let error: Option<&str> = Some("Too short");
+    let warning: Option<&str> = None;
+    let flag = &true;
+    let result = html! {
+        input aria-invalid[error] required[warning] checked[flag];
+    };
+    assert_eq!(result.into_string(), "<input aria-invalid checked>");
+}
+
+#[test]
+fn toggle_classes_options() {
+    fn test(error: Option<&str>) -> Markup {
+        html!(p.invalid[error]:valid.note[error.is_some()] { "Testing!" })
+    }
+    assert_eq!(
+        test(Some("Oops")).into_string(),
+        r#"<p class="invalid note">Testing!</p>"#
+    );
+    assert_eq!(test(None).into_string(), r#"<p class="valid">Testing!</p>"#);
+}
+
 #[test]
 fn empty_attributes_question_mark() {
     let result = html! { input checked? disabled?[true]; };
@@ -137,6 +160,25 @@ fn optional_attribute_variable() {
     assert_eq!(result.into_string(), r#"<input value="42">"#);
 }
 
+#[test]
+fn optional_attribute_escaped_reference() {
+    struct User {
+        name: Option<String>,
+    }
+    let user = User {
+        name: Some("<Pinkie & Pie>".to_owned()),
+    };
+    let nobody = User { name: None };
+    let result = html! {
+        input value=[user.name.as_ref()];
+        input value=[nobody.name.as_ref()];
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<input value="&lt;Pinkie &amp; Pie&gt;"><input>"#
+    );
+}
+
 #[test]
 fn optional_attribute_inner_value_evaluated_only_once() {
     let mut count = 0;
@@ -153,6 +195,123 @@ fn optional_attribute_braces() {
     assert_eq!(result.into_string(), r#"<input value="9000">"#);
 }
 
+#[test]
+fn optional_attribute_fallback() {
+    let name: Option<&str> = None;
+    let size: Option<u32> = Some(3);
+    let result = html! {
+        input placeholder=[name ?? "<anonymous>"] size=[size ?? 20];
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<input placeholder="&lt;anonymous&gt;" size="3">"#
+    );
+}
+
+#[test]
+fn optional_attribute_fallback_after_try() {
+    // `None` if the user is unknown, or `Some(None)` if they have no size set
+    fn lookup(id: u32) -> Option<Option<u32>> {
+        match id {
+            1 => Some(Some(40)),
+            2 => Some(None),
+            _ => None,
+        }
+    }
+    fn render(id: u32) -> Option<String> {
+        Some(html! { input size=[lookup(id)? ?? 20]; }.into_string())
+    }
+    assert_eq!(render(1).as_deref(), Some(r#"<input size="40">"#));
+    assert_eq!(render(2).as_deref(), Some(r#"<input size="20">"#));
+    assert_eq!(render(3), None);
+}
+
+#[test]
+fn toggled_attribute_values() {
+    fn test(show: bool) -> Markup {
+        let v = "<kumquat>";
+        html! { input value=(v)[show] title="Fruit"[show] name={ "fruit-" (v.len()) }[!show]; }
+    }
+    assert_eq!(
+        test(true).into_string(),
+        r#"<input value="&lt;kumquat&gt;" title="Fruit">"#
+    );
+    assert_eq!(test(false).into_string(), r#"<input name="fruit-9">"#);
+}
+
+#[test]
+fn toggled_attribute_value_not_evaluated_when_off() {
+    let mut count = 0;
+    let result = html! { input value=({ count += 1; count })[false]; };
+    assert_eq!(result.into_string(), "<input>");
+    assert_eq!(count, 0);
+}
+
+#[test]
+fn env_values() {
+    let result = html! {
+        meta name="generator" content={ @env("CARGO_PKG_NAME") " " @env("CARGO_PKG_VERSION") };
+        p { @env("CARGO_PKG_NAME") }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<meta name="generator" content="maud "#,
+            env!("CARGO_PKG_VERSION"),
+            r#""><p>maud</p>"#,
+        )
+    );
+}
+
+#[test]
+fn stylesheet_if_exists() {
+    let result = html! {
+        head {
+            @stylesheet_if_exists("tests/static/theme.css")
+            @stylesheet_if_exists("tests/static/theme.css", "/theme.css?v=\"1\"")
+            @stylesheet_if_exists("tests/static/missing.css")
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<head>",
+            r#"<link rel="stylesheet" href="tests/static/theme.css">"#,
+            r#"<link rel="stylesheet" href="/theme.css?v=&quot;1&quot;">"#,
+            "</head>",
+        )
+    );
+}
+
+#[test]
+fn img_dimensions() {
+    let result = html! {
+        @img("tests/static/pony.png");
+        @img("tests/static/pony.png", "/pony.png") alt="A pony" .avatar;
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<img src="tests/static/pony.png" width="3" height="2">"#,
+            r#"<img class="avatar" src="/pony.png" width="3" height="2" alt="A pony">"#,
+        )
+    );
+}
+
+#[test]
+fn raw_attributes() {
+    let raw = PreEscaped(r#"type="search" name="q""#.to_owned());
+    let empty = PreEscaped("");
+    let result = html! {
+        input.search (raw) autofocus;
+        p (empty) {}
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<input class="search" autofocus type="search" name="q"><p></p>"#
+    );
+}
+
 #[test]
 fn colons_in_names() {
     let result = html! { pon-pon:controls-alpha { a on:click="yay()" { "Yay!" } } };
@@ -302,6 +461,64 @@ fn toggle_classes_string() {
     assert_eq!(result.into_string(), r#"<p class="cupcake">Testing!</p>"#);
 }
 
+#[test]
+fn toggle_classes_else() {
+    fn test(is_active: bool) -> Markup {
+        html!(p.tab.active[is_active]:inactive."is-open"[!is_active]:"is-closed" { "Tab" })
+    }
+    assert_eq!(
+        test(true).into_string(),
+        r#"<p class="tab active is-closed">Tab</p>"#
+    );
+    assert_eq!(
+        test(false).into_string(),
+        r#"<p class="tab inactive is-open">Tab</p>"#
+    );
+}
+
+#[test]
+fn toggle_classes_else_first() {
+    let level = 3;
+    let result = html!(p.high[level > 5]:(format!("level-{}", level)).big[level > 1] {});
+    assert_eq!(result.into_string(), r#"<p class="level-3 big"></p>"#);
+}
+
+#[test]
+fn toggle_groups() {
+    fn test(open: bool) -> Markup {
+        html! {
+            button.menu @toggle(open) { .expanded aria-expanded="true" } @else { aria-expanded="false" } {
+                "Menu"
+            }
+        }
+    }
+    assert_eq!(
+        test(true).into_string(),
+        r#"<button class="menu expanded" aria-expanded="true">Menu</button>"#
+    );
+    assert_eq!(
+        test(false).into_string(),
+        r#"<button class="menu" aria-expanded="false">Menu</button>"#
+    );
+}
+
+#[test]
+fn toggle_groups_evaluate_condition_once() {
+    let mut calls = 0;
+    let mut check = || {
+        calls += 1;
+        true
+    };
+    let result = html! {
+        div @toggle(check()) { .shown #panel hidden[false] data-state="on" } {}
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<div class="shown" id="panel" data-state="on"></div>"#
+    );
+    assert_eq!(calls, 1);
+}
+
 #[test]
 fn mixed_classes() {
     fn test(is_muffin: bool) -> Markup {
@@ -373,3 +590,77 @@ fn div_shorthand_id_with_attrs() {
         r#"<div class="awesome-class" id="unique-id" contenteditable dir="rtl"></div>"#
     );
 }
+
+#[test]
+fn single_quoted_attributes() {
+    let title = "it's \"quoted\"";
+    let result = maud::html_single_quoted! {
+        p."it's" title=(title) data-note="Pinkie's" hidden { "don't escape text" }
+        input value=[Some("'")];
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<p class='it&#39;s' title='it&#39;s &quot;quoted&quot;' data-note='Pinkie&#39;s' hidden>"#,
+            r#"don't escape text</p>"#,
+            r#"<input value='&#39;'>"#,
+        )
+    );
+}
+
+#[test]
+fn frontend_framework_attributes() {
+    let result = html! {
+        div x-data="{ open: false }" {
+            button hx-get="/clicked" hx-target="#result" "@click.prevent"="open = !open" {
+                "Toggle"
+            }
+            span ":class"="{ active: open }" x-show="open" {}
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<div x-data="{ open: false }">"#,
+            r##"<button hx-get="/clicked" hx-target="#result" @click.prevent="open = !open">"##,
+            "Toggle</button>",
+            r#"<span :class="{ active: open }" x-show="open"></span>"#,
+            "</div>",
+        )
+    );
+}
+
+#[test]
+fn microdata() {
+    let price = "9.99";
+    let result = html! {
+        @itemscope("https://schema.org/Product?a=1&b=2") div.product {
+            @itemprop("name") h2 { "Cupcake" }
+            @itemprop("offers") @itemscope("https://schema.org/Offer") p {
+                @itemprop("price") span { (price) }
+            }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<div class="product" itemscope itemtype="https://schema.org/Product?a=1&amp;b=2">"#,
+            r#"<h2 itemprop="name">Cupcake</h2>"#,
+            r#"<p itemprop="offers" itemscope itemtype="https://schema.org/Offer">"#,
+            r#"<span itemprop="price">9.99</span>"#,
+            "</p></div>",
+        )
+    );
+}
+
+#[test]
+fn microdata_dynamic() {
+    let schema_type = "Person";
+    let result = html! {
+        @itemscope(format!("https://schema.org/{}", schema_type)) meta content="x";
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<meta itemscope itemtype="https://schema.org/Person" content="x">"#
+    );
+}
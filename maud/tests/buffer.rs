@@ -0,0 +1,74 @@
+use std::marker::PhantomData;
+
+use maud::{html, html_minify, Buffer, PreEscaped};
+
+/// A string stored inline, which remembers the capacity it was created with.
+struct Inline {
+    bytes: [u8; 64],
+    len: usize,
+    capacity: usize,
+}
+
+impl Buffer for Inline {
+    fn with_capacity(capacity: usize) -> Inline {
+        Inline {
+            bytes: [0; 64],
+            len: 0,
+            capacity,
+        }
+    }
+
+    fn push_str(&mut self, s: &str) {
+        self.bytes[self.len..self.len + s.len()].copy_from_slice(s.as_bytes());
+        self.len += s.len();
+    }
+}
+
+impl AsRef<str> for Inline {
+    fn as_ref(&self) -> &str {
+        std::str::from_utf8(&self.bytes[..self.len]).unwrap()
+    }
+}
+
+#[test]
+fn custom_buffer() {
+    let name = "<Maud>";
+    let result = html!(buffer = Inline, p title=(name) { "Hi " (name) "!" });
+    assert_eq!(
+        result.0.as_ref(),
+        r#"<p title="&lt;Maud&gt;">Hi &lt;Maud&gt;!</p>"#
+    );
+    assert!(result.0.capacity > 0);
+}
+
+#[test]
+fn custom_buffer_renders() {
+    let inner = html!(buffer = Inline, b { "Hi" });
+    let result = html! { p { (inner) } };
+    assert_eq!(result.into_string(), "<p><b>Hi</b></p>");
+}
+
+#[test]
+fn custom_buffer_with_generic_type() {
+    struct Tagged<T, Tag>(T, PhantomData<Tag>);
+
+    impl<T: Buffer, Tag> Buffer for Tagged<T, Tag> {
+        fn with_capacity(capacity: usize) -> Self {
+            Tagged(T::with_capacity(capacity), PhantomData)
+        }
+
+        fn push_str(&mut self, s: &str) {
+            self.0.push_str(s);
+        }
+    }
+
+    let result: PreEscaped<Tagged<String, ()>> =
+        html_minify!(buffer = Tagged<String, ()>, ul { li { "a" } li { "b" } });
+    assert_eq!(result.0 .0, "<ul><li>a<li>b</ul>");
+}
+
+#[test]
+fn buffer_element_is_still_an_element() {
+    let result = html! { buffer { "x" } };
+    assert_eq!(result.into_string(), "<buffer>x</buffer>");
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "bumpalo")]
+
+use bumpalo_dep::Bump;
+use maud::{html_in, PreEscaped};
+
+#[test]
+fn html_in_arena() {
+    let bump = Bump::new();
+    let name = "Pinkie & Pie";
+    let result = html_in!(&bump, p.greeting title=(name) { "Hello, " (name) "!" });
+    assert_eq!(
+        result.0.as_str(),
+        r#"<p class="greeting" title="Pinkie &amp; Pie">Hello, Pinkie &amp; Pie!</p>"#
+    );
+}
+
+#[test]
+fn html_in_nested() {
+    let bump = Bump::new();
+    let inner = html_in!(&bump, b { "<bold>" });
+    let outer = html_in!(&bump, p { (inner.0) (PreEscaped("<br>")) });
+    assert_eq!(
+        outer.0.as_str(),
+        "<p>&lt;b&gt;&amp;lt;bold&amp;gt;&lt;/b&gt;<br></p>"
+    );
+}
@@ -1,4 +1,4 @@
-use maud::html;
+use maud::{html, html_with};
 
 #[test]
 fn if_expr() {
@@ -33,6 +33,113 @@ fn if_expr_in_class() {
     }
 }
 
+#[test]
+fn if_expr_shared_splice() {
+    use std::cell::Cell;
+    let calls = Cell::new(0);
+    let expensive = || {
+        calls.set(calls.get() + 1);
+        "<total>"
+    };
+    for (wide, expected) in [
+        (true, "<table><td>&lt;total&gt;</td></table>"),
+        (false, "<p>Total: &lt;total&gt;</p>"),
+    ] {
+        calls.set(0);
+        let result = html! {
+            @if wide {
+                table { td { (expensive()) } }
+            } @else {
+                p { "Total: " (expensive()) }
+            }
+        };
+        assert_eq!(result.into_string(), expected);
+        assert_eq!(calls.get(), 1);
+    }
+}
+
+#[test]
+fn if_expr_without_else_is_not_hoisted() {
+    use std::cell::Cell;
+    let calls = Cell::new(0);
+    let expensive = || {
+        calls.set(calls.get() + 1);
+        "total"
+    };
+    let (first, second) = (false, false);
+    let result = html! {
+        @if first {
+            (expensive())
+        } @else if second {
+            "Total: " (expensive())
+        }
+    };
+    assert_eq!(result.into_string(), "");
+    assert_eq!(calls.get(), 0);
+}
+
+#[test]
+fn if_expr_shared_splice_in_loop() {
+    for (items, expected) in [(&[][..], "none"), (&[1][..], "1"), (&[1, 2][..], "1,2,")] {
+        let result = html! {
+            @if items.is_empty() {
+                "none"
+            } @else if items.len() == 1 {
+                @for item in items { (item) }
+            } @else {
+                @for item in items { (item) "," }
+            }
+        };
+        assert_eq!(result.into_string(), expected);
+    }
+}
+
+#[test]
+fn if_expr_shared_splice_after_condition() {
+    let mut it = [1, 2, 3].iter();
+    let result = html! {
+        @if it.next().is_some() {
+            (it.len())
+        } @else {
+            (it.len())
+        }
+    };
+    assert_eq!(result.into_string(), "2");
+}
+
+#[test]
+fn if_expr_repeated_splice() {
+    let mut it = [1, 2].iter();
+    let result = html! {
+        @if true {
+            (it.next().unwrap()) "," (it.next().unwrap())
+        } @else {
+            (it.next().unwrap())
+        }
+    };
+    assert_eq!(result.into_string(), "1,2");
+}
+
+#[test]
+fn if_expr_shared_field() {
+    struct Item {
+        name: &'static str,
+        stock: Vec<u32>,
+    }
+    let mut item = Item {
+        name: "<widget>",
+        stock: vec![1],
+    };
+    let result = html! {
+        @if item.stock.pop().is_some() {
+            p { (item.name) ": " (item.stock.len()) }
+        } @else {
+            p.sold-out { (item.name) }
+        }
+    };
+    assert_eq!(result.into_string(), "<p>&lt;widget&gt;: 0</p>");
+}
+
 #[test]
 fn if_let() {
     for &(input, output) in &[(Some("yay"), "yay"), (None, "oh noes")] {
@@ -104,6 +211,52 @@ fn for_expr() {
     );
 }
 
+#[test]
+fn for_expr_first_last() {
+    let ponies = ["Apple Bloom", "Scootaloo", "Sweetie Belle"];
+    let result = html! {
+        @for pony in &ponies {
+            @if @first { "→ " } @else { ", " }
+            span.pony.last[@last] { (pony) }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "→ <span class=\"pony\">Apple Bloom</span>",
+            ", <span class=\"pony\">Scootaloo</span>",
+            ", <span class=\"pony last\">Sweetie Belle</span>",
+        )
+    );
+}
+
+#[test]
+fn for_expr_first_last_single_and_empty() {
+    let one = ["a"];
+    let none: [&str; 0] = [];
+    let result = html! {
+        @for item in one.iter().chain(&none) {
+            (item) (@first) (@last)
+        }
+    };
+    assert_eq!(result.into_string(), "atruetrue");
+}
+
+#[test]
+fn for_expr_first_last_nested() {
+    let rows = [["a", "b"], ["c", "d"]];
+    let result = html! {
+        @for row in &rows {
+            @if !@first { "|" }
+            @for cell in row {
+                (cell) @if !@last { "," }
+            }
+            @if @last { "." }
+        }
+    };
+    assert_eq!(result.into_string(), "a,b|c,d.");
+}
+
 #[test]
 fn match_expr() {
     for &(input, output) in &[(Some("yay"), "<div>yay</div>"), (None, "oh noes")] {
@@ -212,3 +365,281 @@ fn let_type_ascription() {
     };
     assert_eq!(result.into_string(), "I have 42 cupcakes!");
 }
+
+#[test]
+fn for_expr_nested_tuple_pattern() {
+    let names = ["Applejack", "Rarity"];
+    let jobs = ["farmer", "designer"];
+    let result = html! {
+        @for (i, (&name, &job)) in names.iter().zip(jobs.iter()).enumerate() {
+            (i) ": " (name) " the " (job) ";"
+        }
+    };
+    let mut expected = String::new();
+    for (i, (&name, &job)) in names.iter().zip(jobs.iter()).enumerate() {
+        expected.push_str(&format!("{}: {} the {};", i, name, job));
+    }
+    assert_eq!(result.into_string(), expected);
+}
+
+#[test]
+fn let_else() {
+    let render = |input: Option<&str>| {
+        html! {
+            p { "before" }
+            div {
+                @let Some(name) = input else {
+                    "nobody"
+                };
+                "Hello, " (name) "!"
+            }
+            p { "after" }
+        }
+        .into_string()
+    };
+    assert_eq!(
+        render(Some("Pinkie")),
+        "<p>before</p><div>Hello, Pinkie!</div><p>after</p>"
+    );
+    assert_eq!(render(None), "<p>before</p><div>nobody</div><p>after</p>");
+}
+
+#[test]
+fn let_else_nested() {
+    let render = |a: Option<i32>, b: Option<i32>| {
+        html! {
+            @let Some(a) = a else { "no a" };
+            @let Some(b) = b else { "no b for " (a) };
+            (a + b)
+        }
+        .into_string()
+    };
+    assert_eq!(render(Some(1), Some(2)), "3");
+    assert_eq!(render(Some(1), None), "no b for 1");
+    assert_eq!(render(None, Some(2)), "no a");
+}
+
+#[test]
+fn let_with_if_else_expr() {
+    let flag = false;
+    let result = html! {
+        @let x = if flag { "yes" } else { "no" };
+        (x)
+    };
+    assert_eq!(result.into_string(), "no");
+}
+
+#[test]
+fn nested_for_over_borrowed_vecs() {
+    struct Context {
+        title: String,
+        rows: Vec<Vec<String>>,
+    }
+    fn render(ctx: &Context) -> String {
+        html! {
+            h1 { (ctx.title) }
+            @for row in &ctx.rows {
+                @if !row.is_empty() {
+                    ul {
+                        @for cell in row {
+                            li { (cell) " of " (ctx.title) }
+                        }
+                    }
+                }
+            }
+        }
+        .into_string()
+    }
+    let ctx = Context {
+        title: "grid".to_owned(),
+        rows: vec![vec!["a".to_owned(), "b".to_owned()], vec![]],
+    };
+    let first = render(&ctx);
+    // The context is still usable, so nothing was moved
+    assert_eq!(first, render(&ctx));
+    assert_eq!(
+        first,
+        "<h1>grid</h1><ul><li>a of grid</li><li>b of grid</li></ul>"
+    );
+    assert_eq!(ctx.rows.len(), 2);
+}
+
+#[test]
+fn unless_expr() {
+    for &(items, expected) in &[
+        (&["a", "b"][..], "<ul><li>a</li><li>b</li></ul>"),
+        (&[][..], "<p>Nothing here</p>"),
+    ] {
+        let result = html! {
+            @unless items.is_empty() {
+                ul {
+                    @for item in items {
+                        li { (item) }
+                    }
+                }
+            } @else {
+                p { "Nothing here" }
+            }
+        };
+        assert_eq!(result.into_string(), expected);
+    }
+}
+
+#[test]
+fn unless_else_if() {
+    for &(n, expected) in &[(0, "zero"), (1, "one"), (2, "many")] {
+        let (positive, negative) = (n > 0, n < 0);
+        let result = html! {
+            @unless positive || negative {
+                "zero"
+            } @else if n == 1 {
+                "one"
+            } @else {
+                "many"
+            }
+        };
+        assert_eq!(result.into_string(), expected);
+    }
+}
+
+#[test]
+fn define_and_use() {
+    let result = html! {
+        @define greeting(name: &str) {
+            p { "Hi " (name) }
+        }
+        @use greeting("Sam")
+        @use greeting("<Alex>")
+    };
+    assert_eq!(result.into_string(), "<p>Hi Sam</p><p>Hi &lt;Alex&gt;</p>");
+}
+
+#[test]
+fn define_multiple_args() {
+    use std::collections::HashMap;
+    let mut stock = HashMap::new();
+    stock.insert("apples", 3);
+    let result = html! {
+        @define row(label: &str, counts: &HashMap<&str, i32>) {
+            tr {
+                td { (label) }
+                td { (counts.get(label).copied().unwrap_or(0)) }
+            }
+        }
+        table {
+            @use row("apples", &stock)
+            @use row("pears", &stock)
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        "<table><tr><td>apples</td><td>3</td></tr><tr><td>pears</td><td>0</td></tr></table>"
+    );
+}
+
+#[test]
+fn define_captures_and_nests() {
+    let site = "Ponyville";
+    let result = html! {
+        @define title(page: &str) {
+            (page) " | " (site)
+        }
+        @define heading(page: &str) {
+            h1 { @use title(page) }
+        }
+        @for page in &["Home", "About"] {
+            @use heading(page)
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        "<h1>Home | Ponyville</h1><h1>About | Ponyville</h1>"
+    );
+}
+
+#[test]
+// The inner `word` is shadowed on purpose
+#[cfg_attr(feature = "lints", allow(deprecated))]
+fn define_is_scoped_to_block() {
+    let result = html! {
+        @define word() { "outer" }
+        {
+            @define word() { "inner" }
+            @use word()
+        }
+        " "
+        @use word()
+    };
+    assert_eq!(result.into_string(), "inner outer");
+}
+
+struct RenderFlags {
+    supports_webp: bool,
+    is_mobile: bool,
+    beta: Option<&'static str>,
+}
+
+#[test]
+fn flag_expr() {
+    fn page(flags: &RenderFlags) -> String {
+        html_with!(flags,
+            @flag(supports_webp) { img src="a.webp"; } @else { img src="a.png"; }
+            @flag(!is_mobile) { nav { "Menu" } }
+            @flag(beta) { "β" }
+        )
+        .into_string()
+    }
+    let desktop = RenderFlags {
+        supports_webp: true,
+        is_mobile: false,
+        beta: Some("on"),
+    };
+    assert_eq!(page(&desktop), r#"<img src="a.webp"><nav>Menu</nav>β"#);
+    let mobile = RenderFlags {
+        supports_webp: false,
+        is_mobile: true,
+        beta: None,
+    };
+    assert_eq!(page(&mobile), r#"<img src="a.png">"#);
+}
+
+#[test]
+fn flag_expr_context_evaluated_once() {
+    let mut count = 0;
+    let flags = RenderFlags {
+        supports_webp: true,
+        is_mobile: true,
+        beta: None,
+    };
+    let result = html_with!({ count += 1; &flags },
+        @flag(supports_webp) { "a" }
+        @flag(is_mobile) { "b" }
+    );
+    assert_eq!(result.into_string(), "ab");
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn debug_only_and_release_only() {
+    let result = html! {
+        p {
+            "Hi"
+            @debug_only { " (debug build, " (1 + 1) " queries)" }
+            @release_only { "!" }
+        }
+    };
+    if cfg!(debug_assertions) {
+        assert_eq!(result.into_string(), "<p>Hi (debug build, 2 queries)</p>");
+    } else {
+        assert_eq!(result.into_string(), "<p>Hi!</p>");
+    }
+}
+
+#[test]
+fn debug_only_and_release_only_share_ids() {
+    let result = html! {
+        @debug_only { div #banner { "Debug" } }
+        @release_only { div #banner { "Release" } }
+    };
+    assert!(result.into_string().starts_with(r#"<div id="banner">"#));
+}
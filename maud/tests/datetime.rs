@@ -0,0 +1,48 @@
+use maud::{html, time_element};
+
+#[test]
+fn time_element_escapes() {
+    let result = time_element(r#"2020-01-01" onclick="x"#, "<New Year>");
+    assert_eq!(
+        result.into_string(),
+        r#"<time datetime="2020-01-01&quot; onclick=&quot;x">&lt;New Year&gt;</time>"#
+    );
+}
+
+#[test]
+fn time_element_with_markup() {
+    let result = time_element("PT2H30M", html! { b { "2½ hours" } });
+    assert_eq!(
+        result.into_string(),
+        r#"<time datetime="PT2H30M"><b>2½ hours</b></time>"#
+    );
+}
+
+#[cfg(feature = "chrono")]
+mod chrono {
+    use chrono_dep::{FixedOffset, NaiveDate, TimeZone};
+
+    #[test]
+    fn timestamp_date_time() {
+        let offset = FixedOffset::east_opt(10 * 3600).unwrap();
+        let datetime = offset.with_ymd_and_hms(2011, 11, 18, 14, 54, 39).unwrap();
+        assert_eq!(
+            maud::timestamp(&datetime).into_string(),
+            r#"<time datetime="2011-11-18T14:54:39+10:00">18 November 2011, 14:54</time>"#
+        );
+    }
+
+    #[test]
+    fn timestamp_naive() {
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        assert_eq!(
+            maud::timestamp(&date).into_string(),
+            r#"<time datetime="2024-02-29">29 February 2024</time>"#
+        );
+        let datetime = date.and_hms_opt(9, 5, 0).unwrap();
+        assert_eq!(
+            maud::timestamp(&datetime).into_string(),
+            r#"<time datetime="2024-02-29T09:05:00">29 February 2024, 09:05</time>"#
+        );
+    }
+}
@@ -0,0 +1,36 @@
+#![cfg(feature = "email")]
+
+use maud::{email_html, email_html_with, html, EmailOptions};
+
+#[test]
+fn wraps_body() {
+    let name = "<Rarity>";
+    let result = email_html(html! { p { "Hi, " (name) } });
+    let result = result.into_string();
+    assert!(result.starts_with("<!DOCTYPE html><html><head><meta charset=\"utf-8\">"));
+    assert!(result.ends_with("<body><p>Hi, &lt;Rarity&gt;</p></body></html>"));
+}
+
+#[test]
+fn inlines_css() {
+    let name = "<Rarity>";
+    let body = html! {
+        style { "p { color: purple } .name { font-weight: bold }" }
+        p { "Hi, " span.name { (name) } }
+    };
+    let result = email_html_with(body, EmailOptions { inline_css: true }).unwrap();
+    let result = result.into_string();
+    assert!(!result.contains("<style>"));
+    assert!(result.ends_with(concat!(
+        r#"<body><p style="color: purple;">Hi, "#,
+        r#"<span class="name" style="font-weight: bold;">&lt;Rarity&gt;</span></p>"#,
+        "</body></html>",
+    )));
+}
+
+#[test]
+fn inlining_is_optional() {
+    let body = html! { style { "p { color: purple }" } p { "Hi" } };
+    let plain = email_html_with(&body, EmailOptions::default()).unwrap();
+    assert_eq!(plain.into_string(), email_html(&body).into_string());
+}
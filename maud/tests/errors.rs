@@ -5,3 +5,10 @@ fn run_warnings() {
     let config = TestCases::new();
     config.compile_fail("tests/warnings/*.rs");
 }
+
+#[cfg(feature = "lints")]
+#[test]
+fn run_lints() {
+    let config = TestCases::new();
+    config.compile_fail("tests/lints/*.rs");
+}
@@ -0,0 +1,74 @@
+use core::fmt::{self, Write};
+
+use maud::{html, html_fmt, Render};
+
+/// A writer that fails once it holds more than `limit` bytes.
+struct Limited {
+    output: String,
+    limit: usize,
+}
+
+impl Write for Limited {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        if self.output.len() + s.len() > self.limit {
+            return Err(fmt::Error);
+        }
+        self.output.push_str(s);
+        Ok(())
+    }
+}
+
+#[test]
+fn html_fmt() {
+    let name = "<Pinkie>";
+    let mut output = String::new();
+    let result = html_fmt!(&mut output, p title=(name) { "Hi " (name) "!" });
+    assert_eq!(result, Ok(()));
+    assert_eq!(
+        output,
+        r#"<p title="&lt;Pinkie&gt;">Hi &lt;Pinkie&gt;!</p>"#
+    );
+}
+
+#[test]
+fn html_fmt_in_display() {
+    struct Greeting<'a>(&'a str);
+
+    impl fmt::Display for Greeting<'_> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            html_fmt!(f, b { (self.0) })
+        }
+    }
+
+    assert_eq!(Greeting("a & b").to_string(), "<b>a &amp; b</b>");
+}
+
+#[test]
+fn html_fmt_propagates_errors() {
+    let mut writer = Limited {
+        output: String::new(),
+        limit: 10,
+    };
+    let result = html_fmt!(&mut writer, p { ("ok") (1) } p { ("never written") });
+    assert_eq!(result, Err(fmt::Error));
+    assert_eq!(writer.output, "<p>ok1");
+}
+
+#[test]
+fn render_to_fmt() {
+    let markup = html! { p { "Hello" } };
+    let mut output = String::new();
+    assert_eq!(markup.render_to_fmt(&mut output), Ok(()));
+    assert_eq!("<&>".render_to_fmt(&mut output), Ok(()));
+    assert_eq!(output, "<p>Hello</p>&lt;&amp;&gt;");
+
+    let mut writer = Limited {
+        output: String::new(),
+        limit: 4,
+    };
+    assert_eq!(markup.render_to_fmt(&mut writer), Err(fmt::Error));
+    assert_eq!(
+        Box::new(&markup).render_to_fmt(&mut writer),
+        Err(fmt::Error)
+    );
+}
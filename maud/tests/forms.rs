@@ -0,0 +1,139 @@
+use maud::html;
+
+#[test]
+fn select_options() {
+    let options = [("a", "Apple"), ("b", "Banana"), ("c", "Cherry")];
+    let result = html! {
+        select { (maud::select_options(&options, Some("b"))) }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<select>",
+            r#"<option value="a">Apple</option>"#,
+            r#"<option value="b" selected>Banana</option>"#,
+            r#"<option value="c">Cherry</option>"#,
+            "</select>",
+        )
+    );
+}
+
+#[test]
+fn select_options_none_selected() {
+    let options = vec![("a".to_owned(), "Apple".to_owned())];
+    let result = maud::select_options(&options, None);
+    assert_eq!(result.into_string(), r#"<option value="a">Apple</option>"#);
+}
+
+#[test]
+fn select_options_escaping() {
+    let options = [(r#"1" onclick="x"#, "<b>Bold & brash</b>")];
+    let result = maud::select_options(&options, Some(r#"1" onclick="x"#));
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<option value="1&quot; onclick=&quot;x" selected>"#,
+            "&lt;b&gt;Bold &amp; brash&lt;/b&gt;</option>",
+        )
+    );
+}
+
+#[test]
+fn error_summary() {
+    let errors = [("name", "Enter your name"), ("age", "Age must be a number")];
+    assert_eq!(
+        maud::error_summary(&errors).into_string(),
+        concat!(
+            r#"<div role="alert"><ul>"#,
+            r##"<li><a href="#name">Enter your name</a></li>"##,
+            r##"<li><a href="#age">Age must be a number</a></li>"##,
+            "</ul></div>",
+        )
+    );
+}
+
+#[test]
+fn error_summary_empty() {
+    let errors: Vec<(String, String)> = Vec::new();
+    assert_eq!(maud::error_summary(&errors).into_string(), "");
+}
+
+#[test]
+fn error_summary_escaping() {
+    let errors = [(r#"x" onclick="y"#, "Must be < 10 & > 0")];
+    assert_eq!(
+        maud::error_summary(&errors).into_string(),
+        concat!(
+            r#"<div role="alert"><ul>"#,
+            r##"<li><a href="#x&quot; onclick=&quot;y">Must be &lt; 10 &amp; &gt; 0</a></li>"##,
+            "</ul></div>",
+        )
+    );
+}
+
+#[derive(maud::MaudForm)]
+struct Signup {
+    user_name: String,
+    age: u32,
+    height: Option<f64>,
+    newsletter: bool,
+    r#type: &'static str,
+}
+
+#[test]
+fn derive_form() {
+    let form = Signup {
+        user_name: "<Pinkie>".to_owned(),
+        age: 21,
+        height: None,
+        newsletter: true,
+        r#type: "party",
+    };
+    assert_eq!(
+        form.render_fields().into_string(),
+        concat!(
+            r#"<div><label for="user_name">User name</label>"#,
+            r#"<input id="user_name" name="user_name" type="text" value="&lt;Pinkie&gt;"></div>"#,
+            r#"<div><label for="age">Age</label>"#,
+            r#"<input id="age" name="age" type="number" value="21"></div>"#,
+            r#"<div><label for="height">Height</label>"#,
+            r#"<input id="height" name="height" type="number" step="any"></div>"#,
+            r#"<div><label for="newsletter">Newsletter</label>"#,
+            r#"<input id="newsletter" name="newsletter" type="checkbox" checked></div>"#,
+            r#"<div><label for="type">Type</label>"#,
+            r#"<input id="type" name="type" type="text" value="party"></div>"#,
+        )
+    );
+}
+
+#[test]
+fn labeled_input() {
+    let mut ids = maud::FieldIds::new("login");
+    let result = html! {
+        (maud::labeled_input(&mut ids, "User <name>", &[("name", "user")]))
+        (maud::labeled_input(&mut ids, "Password", &[("type", "password"), ("title", r#"a"b"#)]))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<label for="login-0">User &lt;name&gt;</label><input id="login-0" name="user">"#,
+            r#"<label for="login-1">Password</label>"#,
+            r#"<input id="login-1" type="password" title="a&quot;b">"#,
+        )
+    );
+}
+
+#[test]
+fn labeled_input_given_id() {
+    let mut ids = maud::FieldIds::new("f");
+    let attrs = [("type", "checkbox"), ("id", "remember")];
+    assert_eq!(
+        maud::labeled_input(&mut ids, "Remember me", &attrs).into_string(),
+        r#"<label for="remember">Remember me</label><input id="remember" type="checkbox">"#
+    );
+    let empty: [(&str, &str); 0] = [];
+    assert_eq!(
+        maud::labeled_input(&mut ids, "Name", &empty).into_string(),
+        r#"<label for="f-0">Name</label><input id="f-0">"#
+    );
+}
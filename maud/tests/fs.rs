@@ -0,0 +1,20 @@
+#![cfg(feature = "fs")]
+
+use maud::html;
+
+#[test]
+fn write_html_file_adds_newline() {
+    let path = std::env::temp_dir().join(format!("maud-fs-test-{}.html", std::process::id()));
+    maud::write_html_file(&path, html! { p { "Hi" } }).unwrap();
+    let written = std::fs::read_to_string(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(written.unwrap(), "<p>Hi</p>\n");
+}
+
+#[test]
+fn write_html_file_reports_errors() {
+    let path = std::env::temp_dir()
+        .join("maud-fs-test-missing-dir")
+        .join("index.html");
+    assert!(maud::write_html_file(path, html! { p { "Hi" } }).is_err());
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "serde")]
+
+use maud::html;
+use serde_json::{json, Value};
+
+#[test]
+fn ld_json_escapes_script_breakout() {
+    let value = json!({ "name": "</script><!-- & -->" });
+    let result = maud::ld_json(&value).unwrap().into_string();
+    assert!(!result.contains('<'));
+    assert!(!result.contains('>'));
+    assert!(!result.contains('&'));
+    let round_trip: Value = serde_json::from_str(&result).unwrap();
+    assert_eq!(round_trip, value);
+}
+
+#[test]
+fn ld_json_in_script() {
+    let result = html! {
+        script type="application/ld+json" { (maud::ld_json(&[1, 2, 3]).unwrap()) }
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<script type="application/ld+json">[1,2,3]</script>"#
+    );
+}
+
+#[test]
+fn ld_json_error() {
+    use std::collections::BTreeMap;
+    let mut map = BTreeMap::new();
+    map.insert((1, 2), "tuple keys aren't valid JSON");
+    assert!(maud::ld_json(&map).is_err());
+}
+
+#[test]
+fn json_pretty_escapes_text() {
+    let value = json!({ "html": "</pre><script>&</script>" });
+    let result = html! { pre { (maud::json_pretty(&value).unwrap()) } };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<pre>{\n",
+            "  &quot;html&quot;: &quot;&lt;/pre&gt;&lt;script&gt;&amp;&lt;/script&gt;&quot;\n",
+            "}</pre>",
+        )
+    );
+}
@@ -0,0 +1,193 @@
+use maud::{html, Block, Layout, Markup, PreEscaped, Shell};
+
+struct Page {
+    title: &'static str,
+}
+
+impl Layout for Page {
+    fn layout(&self, content: Markup) -> Markup {
+        html! {
+            html {
+                head { title { (self.title) } }
+                body { (content) }
+            }
+        }
+    }
+}
+
+#[test]
+fn prerender_matches_layout() {
+    let page = Page { title: "Ponies" };
+    let content = || html! { p { "Hello & welcome" } };
+    assert_eq!(
+        page.prerender().fill(content()).into_string(),
+        page.layout(content()).into_string(),
+    );
+}
+
+#[test]
+fn fill_escapes_content() {
+    let shell = Page { title: "Ponies" }.prerender();
+    assert_eq!(
+        shell.fill("<b>").into_string(),
+        "<html><head><title>Ponies</title></head><body>&lt;b&gt;</body></html>",
+    );
+}
+
+#[test]
+fn split_with_custom_marker() {
+    let shell = Shell::split(PreEscaped("<div>@@</div>"), "@@").unwrap();
+    assert_eq!(shell.fill(42).into_string(), "<div>42</div>");
+}
+
+#[test]
+fn split_requires_single_marker() {
+    assert!(Shell::split(PreEscaped("<div></div>"), "@@").is_none());
+    assert!(Shell::split(PreEscaped("@@<div></div>@@"), "@@").is_none());
+}
+
+#[test]
+#[should_panic]
+fn prerender_without_content() {
+    let layout = |_: Markup| html! { p { "Nothing to see here" } };
+    layout.prerender();
+}
+
+#[test]
+fn slots_collect_in_order() {
+    let slots = maud::Slots::new();
+    let widget = |name: &str| {
+        html! {
+            @slot(slots, "head") { link rel="stylesheet" href=(format!("/{}.css", name)); }
+            @slot(&slots, "scripts") { script { (name) " & co" } }
+            div.(name) {}
+        }
+    };
+    let body = html! { (widget("a")) (widget("b")) };
+    let page = html! {
+        head { @outlet(slots, "head") }
+        body { (body) @outlet(slots, "scripts") }
+        footer { @outlet(slots, "head") @outlet(slots, "missing") }
+    };
+    assert_eq!(
+        page.into_string(),
+        concat!(
+            r#"<head><link rel="stylesheet" href="/a.css"><link rel="stylesheet" href="/b.css"></head>"#,
+            r#"<body><div class="a"></div><div class="b"></div>"#,
+            "<script>a &amp; co</script><script>b &amp; co</script></body>",
+            "<footer></footer>",
+        )
+    );
+}
+
+#[test]
+fn slots_in_html_single_quoted() {
+    let slots = maud::Slots::new();
+    let inner = maud::html_single_quoted! {
+        @slot(slots, "x") { a title=("it's") {} }
+    };
+    assert_eq!(inner.into_string(), "");
+    assert_eq!(slots.take("x").into_string(), "<a title='it&#39;s'></a>");
+}
+
+#[test]
+fn html_document_default_lang() {
+    let page = maud::html_document(None, html! { title { "Hi" } }, "<body>");
+    assert_eq!(
+        page.into_string(),
+        concat!(
+            r#"<!DOCTYPE html><html lang="en">"#,
+            "<head><title>Hi</title></head>",
+            "<body>&lt;body&gt;</body></html>",
+        ),
+    );
+}
+
+#[test]
+fn html_document_escapes_lang() {
+    let page = maud::html_document(Some(r#"x"><script>"#), "", "");
+    assert_eq!(
+        page.into_string(),
+        r#"<!DOCTYPE html><html lang="x&quot;&gt;&lt;script&gt;"><head></head><body></body></html>"#,
+    );
+}
+
+#[derive(Default)]
+struct Base {
+    title: Block,
+    content: Block,
+    sidebar: Block,
+}
+
+fn base(page: Base) -> Markup {
+    html! {
+        title { @block(page.title) { "Ponyville" } }
+        aside { @block(&page.sidebar) { a href="/" { "Home" } } }
+        main { @block(page.content) {} }
+    }
+}
+
+#[test]
+fn blocks_inherit_defaults() {
+    assert_eq!(
+        base(Base::default()).into_string(),
+        r#"<title>Ponyville</title><aside><a href="/">Home</a></aside><main></main>"#
+    );
+}
+
+#[test]
+fn blocks_override() {
+    let page = base(Base {
+        title: Block::new("<News>"),
+        content: Block::new(html! { p { "Hello!" } }),
+        ..Base::default()
+    });
+    assert_eq!(
+        page.into_string(),
+        concat!(
+            "<title>&lt;News&gt;</title>",
+            r#"<aside><a href="/">Home</a></aside>"#,
+            "<main><p>Hello!</p></main>",
+        )
+    );
+}
+
+#[test]
+fn blocks_extend_twice() {
+    // A layout between `base` and the page, with its own sidebar
+    fn with_sidebar(page: Base) -> Markup {
+        base(Base {
+            sidebar: page.sidebar.or(Block::new(html! { "Archive" })),
+            ..page
+        })
+    }
+    let inherited = with_sidebar(Base {
+        content: Block::new("Hi"),
+        ..Base::default()
+    });
+    assert_eq!(
+        inherited.into_string(),
+        "<title>Ponyville</title><aside>Archive</aside><main>Hi</main>"
+    );
+    let overridden = with_sidebar(Base {
+        sidebar: Block::new("Links"),
+        ..Base::default()
+    });
+    assert_eq!(
+        overridden.into_string(),
+        "<title>Ponyville</title><aside>Links</aside><main></main>"
+    );
+}
+
+#[test]
+fn trailing_newline() {
+    assert_eq!(
+        maud::with_trailing_newline(html! { p { "Hi" } }).into_string(),
+        "<p>Hi</p>\n"
+    );
+    assert_eq!(
+        maud::with_trailing_newline(PreEscaped("<p>Hi</p>\n")).into_string(),
+        "<p>Hi</p>\n"
+    );
+    assert_eq!(maud::with_trailing_newline("").into_string(), "\n");
+}
@@ -0,0 +1,59 @@
+use maud::{html_lazy, PreEscaped};
+
+#[test]
+fn images() {
+    let result = html_lazy! { img src="pinkie.png" alt="Pinkie Pie"; };
+    assert_eq!(
+        result.into_string(),
+        r#"<img src="pinkie.png" alt="Pinkie Pie" loading="lazy" decoding="async">"#
+    );
+}
+
+#[test]
+fn iframes() {
+    let result = html_lazy! { iframe src="/map" {} };
+    assert_eq!(
+        result.into_string(),
+        r#"<iframe src="/map" loading="lazy" decoding="async"></iframe>"#
+    );
+}
+
+#[test]
+fn explicit_attributes_win() {
+    let loading: Option<&str> = None;
+    let result = html_lazy! {
+        img src="a.png" loading="eager";
+        img src="b.png" decoding="sync";
+        img src="c.png" loading=[loading];
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<img src="a.png" loading="eager" decoding="async">"#,
+            r#"<img src="b.png" decoding="sync" loading="lazy">"#,
+            r#"<img src="c.png" decoding="async">"#,
+        )
+    );
+}
+
+#[test]
+fn raw_attributes_turn_off_defaults() {
+    let attrs = PreEscaped(r#"src="a.png" loading="eager""#);
+    let result = html_lazy! { img (attrs); img src="b.png"; };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<img src="a.png" loading="eager">"#,
+            r#"<img src="b.png" loading="lazy" decoding="async">"#,
+        )
+    );
+}
+
+#[test]
+fn other_elements() {
+    let result = html_lazy! { video src="clip.mp4" {} p { img.icon src="x"; } };
+    assert_eq!(
+        result.into_string(),
+        r#"<video src="clip.mp4"></video><p><img class="icon" src="x" loading="lazy" decoding="async"></p>"#
+    );
+}
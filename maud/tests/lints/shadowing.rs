@@ -0,0 +1,25 @@
+#![deny(deprecated)]
+
+use maud::html;
+
+fn main() {
+    let rows = [[1, 2], [3, 4]];
+    html! {
+        @for item in &rows {
+            @for item in item {
+                (item)
+            }
+        }
+        @let (total, _) = (0, 1);
+        p {
+            @let total = total + 1;
+            (total)
+        }
+        @for row in &rows {
+            (row.len())
+        }
+        @for row in &rows {
+            (row.len())
+        }
+    };
+}
@@ -0,0 +1,106 @@
+use maud::{html, Date, Locale, Localizer, Markup};
+
+struct English;
+
+impl Locale for English {
+    fn format_int(&self, value: i128) -> String {
+        group_thousands(&value.to_string(), ',')
+    }
+
+    fn format_float(&self, value: f64) -> String {
+        format!("{:.2}", value)
+    }
+
+    fn format_date(&self, date: Date) -> String {
+        format!("{}/{}/{}", date.month, date.day, date.year)
+    }
+}
+
+struct French;
+
+impl Locale for French {
+    fn format_int(&self, value: i128) -> String {
+        group_thousands(&value.to_string(), '\u{202F}')
+    }
+
+    fn format_float(&self, value: f64) -> String {
+        format!("{:.2}", value).replace('.', ",")
+    }
+
+    fn format_date(&self, date: Date) -> String {
+        format!("{:02}/{:02}/{}", date.day, date.month, date.year)
+    }
+}
+
+fn group_thousands(digits: &str, separator: char) -> String {
+    let (sign, digits) = match digits.strip_prefix('-') {
+        Some(digits) => ("-", digits),
+        None => ("", digits),
+    };
+    let mut result = String::from(sign);
+    for (i, digit) in digits.chars().enumerate() {
+        if i > 0 && (digits.len() - i) % 3 == 0 {
+            result.push(separator);
+        }
+        result.push(digit);
+    }
+    result
+}
+
+fn receipt(l10n: Localizer) -> Markup {
+    html! {
+        p {
+            (l10n.num(1234567)) " items, "
+            (l10n.num(9.5)) " each, on "
+            (l10n.date(Date::new(2024, 3, 7)))
+        }
+    }
+}
+
+#[test]
+fn same_template_different_locales() {
+    assert_eq!(
+        receipt(Localizer::new(&English)).into_string(),
+        r#"<p>1,234,567 items, 9.50 each, on <time datetime="2024-03-07">3/7/2024</time></p>"#
+    );
+    assert_eq!(
+        receipt(Localizer::new(&French)).into_string(),
+        "<p>1\u{202F}234\u{202F}567 items, 9,50 each, on \
+         <time datetime=\"2024-03-07\">07/03/2024</time></p>"
+    );
+}
+
+#[test]
+fn num_types() {
+    assert_eq!(maud::num(&English, -4200i16).into_string(), "-4,200");
+    assert_eq!(
+        maud::num(&English, u64::MAX).into_string(),
+        "18,446,744,073,709,551,615"
+    );
+    assert_eq!(maud::num(&English, 0.125f32).into_string(), "0.12");
+}
+
+struct Shouty;
+
+impl Locale for Shouty {
+    fn format_int(&self, value: i128) -> String {
+        format!("<{}>", value)
+    }
+
+    fn format_float(&self, value: f64) -> String {
+        format!("<{}>", value)
+    }
+
+    fn format_date(&self, date: Date) -> String {
+        format!("<{}>", date.year)
+    }
+}
+
+#[test]
+fn locale_output_is_escaped() {
+    assert_eq!(maud::num(&Shouty, 1).into_string(), "&lt;1&gt;");
+    assert_eq!(
+        maud::date(&Shouty, Date::new(99, 1, 2)).into_string(),
+        r#"<time datetime="0099-01-02">&lt;99&gt;</time>"#
+    );
+}
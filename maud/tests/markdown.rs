@@ -0,0 +1,78 @@
+#![cfg(feature = "markdown")]
+
+use maud::{html, markdown, MarkdownOptions};
+
+#[test]
+fn renders_commonmark() {
+    let result = html! {
+        article { (markdown("# Title\n\nSome *emphasis* and `code`.")) }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<article><h1>Title</h1>\n",
+            "<p>Some <em>emphasis</em> and <code>code</code>.</p>\n",
+            "</article>",
+        ),
+    );
+}
+
+#[test]
+fn html_is_escaped_by_default() {
+    let result = html! {
+        (markdown("<div onclick=\"steal()\">hi</div>\n\nand <b>bold</b>"))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "&lt;div onclick=\"steal()\"&gt;hi&lt;/div&gt;\n",
+            "<p>and &lt;b&gt;bold&lt;/b&gt;</p>\n",
+        ),
+    );
+}
+
+#[test]
+fn unsafe_urls_are_removed_by_default() {
+    let result = html! {
+        (markdown("[a](javascript:alert(1)) [b](JavaScript:x) [c](/about) [d](https://example.com) ![e](data:text/html,hi)"))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<p><a href="">a</a> <a href="">b</a> <a href="/about">c</a> "#,
+            r#"<a href="https://example.com">d</a> <img src="" alt="e" /></p>"#,
+            "\n",
+        ),
+    );
+}
+
+#[test]
+fn raw_html_and_unsafe_urls_can_be_allowed() {
+    let options = MarkdownOptions {
+        raw_html: true,
+        unsafe_urls: true,
+        ..MarkdownOptions::default()
+    };
+    let result = html! {
+        (markdown("<b>bold</b> [a](javascript:void(0))").options(options))
+    };
+    assert_eq!(
+        result.into_string(),
+        "<p><b>bold</b> <a href=\"javascript:void(0)\">a</a></p>\n",
+    );
+}
+
+#[test]
+fn extensions() {
+    let options = MarkdownOptions {
+        tables: true,
+        strikethrough: true,
+        ..MarkdownOptions::default()
+    };
+    let text = "| a |\n| - |\n| b |\n\n~~gone~~";
+    let plain = html! { (markdown(text)) }.into_string();
+    let extended = html! { (markdown(text).options(options)) }.into_string();
+    assert!(!plain.contains("<table>"));
+    assert!(extended.contains("<table>"));
+    assert!(extended.contains("<del>gone</del>"));
+}
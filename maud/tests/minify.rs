@@ -0,0 +1,111 @@
+use maud::{html, html_minify};
+
+#[test]
+fn list_items() {
+    let result = html_minify! {
+        ul { li { "one" } li { "two" } li { "three" } }
+    };
+    assert_eq!(result.into_string(), "<ul><li>one<li>two<li>three</ul>");
+}
+
+#[test]
+fn table() {
+    let result = html_minify! {
+        table {
+            tr { th { "Name" } td { "Pinkie" } }
+            tr { td { "Rarity" } td { "Pinkie" } }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        "<table><tr><th>Name</th><td>Pinkie<tr><td>Rarity<td>Pinkie</table>"
+    );
+}
+
+#[test]
+fn options() {
+    let result = html_minify! {
+        select {
+            option { "a" }
+            optgroup label="more" { option { "b" } option { "c" } }
+            option { "d" }
+            hr;
+            option { "e" }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<select><option>a",
+            r#"<optgroup label="more"><option>b<option>c</optgroup>"#,
+            "<option>d<hr><option>e</select>",
+        )
+    );
+}
+
+#[test]
+fn paragraphs() {
+    let result = html_minify! {
+        div { p { "one" } p { "two" } ul { li { "x" } } p { "three" } }
+        p { "top level" }
+        p { "kept" } span { "inline" }
+        a href="#" { p { "in a link" } }
+        my-element { p { "in a custom element" } }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<div><p>one<p>two<ul><li>x</ul><p>three</div>",
+            "<p>top level",
+            "<p>kept</p><span>inline</span>",
+            r##"<a href="#"><p>in a link</p></a>"##,
+            "<my-element><p>in a custom element</p></my-element>",
+        )
+    );
+}
+
+#[test]
+fn dynamic_siblings_keep_end_tags() {
+    let items = ["a", "b"];
+    let result = html_minify! {
+        ul {
+            li { "first" }
+            @for item in &items {
+                li { (item) }
+            }
+            li { "last" }
+            (items.len())
+        }
+        ul {
+            { li { "in a block" } }
+        }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<ul><li>first</li><li>a</li><li>b</li><li>last</li>2</ul>",
+            "<ul><li>in a block</li></ul>",
+        )
+    );
+}
+
+#[test]
+fn let_else_end_of_parent() {
+    let render = |x: Option<i32>| {
+        html_minify! {
+            ul {
+                @let Some(x) = x else { li { "none" } };
+                li { (x) }
+            }
+        }
+        .into_string()
+    };
+    assert_eq!(render(Some(1)), "<ul><li>1</ul>");
+    assert_eq!(render(None), "<ul><li>none</ul>");
+}
+
+#[test]
+fn html_keeps_end_tags() {
+    let result = html! { ul { li { "one" } li { "two" } } };
+    assert_eq!(result.into_string(), "<ul><li>one</li><li>two</li></ul>");
+}
@@ -143,3 +143,160 @@ fn render_arc() {
     let arc = std::sync::Arc::new("foo");
     assert_eq!(html! { (arc) }.into_string(), "foo");
 }
+
+#[test]
+fn render_rc() {
+    let rc = std::rc::Rc::new("foo");
+    assert_eq!(html! { (rc) }.into_string(), "foo");
+}
+
+#[test]
+fn render_trait_objects() {
+    use maud::Render;
+    use std::{rc::Rc, sync::Arc};
+
+    let boxed: Vec<Box<dyn Render>> = vec![Box::new("<a>"), Box::new(42), Box::new(html! { b {} })];
+    let rc: Rc<dyn Render> = Rc::new("rc");
+    let arc: Arc<dyn Render> = Arc::new("arc");
+    let result = html! {
+        @for view in &boxed {
+            (view)
+        }
+        (rc) (arc)
+    };
+    assert_eq!(result.into_string(), "&lt;a&gt;42<b></b>rcarc");
+}
+
+#[test]
+fn render_slices() {
+    use std::borrow::Cow;
+    let items = vec!["<a>", "b", "<c>"];
+    let borrowed: Cow<[i32]> = Cow::Borrowed(&[1, 2]);
+    let owned: Cow<[i32]> = Cow::Owned(vec![3]);
+    let result = html! {
+        (items.as_slice()) " " (items) " " (borrowed) (owned)
+        (Vec::<maud::Markup>::new())
+    };
+    assert_eq!(
+        result.into_string(),
+        "&lt;a&gt;b&lt;c&gt; &lt;a&gt;b&lt;c&gt; 123"
+    );
+}
+
+#[test]
+fn document_with_bom() {
+    let result = maud::document(maud::BOM, html! { (maud::DOCTYPE) p { "<hi>" } });
+    assert_eq!(
+        result.into_string(),
+        "\u{FEFF}<!DOCTYPE html><p>&lt;hi&gt;</p>"
+    );
+}
+
+#[test]
+fn collect_renders_each_item() {
+    let items = maud::collect(vec![html! { b { "one" } }, html! { "<two>" }, html! {}]);
+    assert_eq!(
+        items.iter().map(|item| item.0.as_str()).collect::<Vec<_>>(),
+        ["<b>one</b>", "&lt;two&gt;", ""]
+    );
+
+    let numbers = maud::collect(1..=3);
+    let result = html! {
+        @for number in numbers.into_iter().rev() {
+            i { (number) }
+        }
+    };
+    assert_eq!(result.into_string(), "<i>3</i><i>2</i><i>1</i>");
+}
+
+#[test]
+fn deterministic_output() {
+    use std::collections::{BTreeMap, HashMap};
+
+    fn render(attrs: &BTreeMap<&str, &str>) -> String {
+        html! {
+            @for (key, value) in attrs {
+                span.b.a #id data-key=(key) title=(value) .c[true] {}
+            }
+        }
+        .into_string()
+    }
+
+    let entries = [("z", "1"), ("a", "2"), ("m", "3")];
+    // Build the data through a `HashMap`, whose iteration order varies
+    let hashed = entries.iter().copied().collect::<HashMap<_, _>>();
+    let sorted = hashed.into_iter().collect::<BTreeMap<_, _>>();
+    let expected = concat!(
+        r#"<span class="b a c" id="id" data-key="a" title="2"></span>"#,
+        r#"<span class="b a c" id="id" data-key="m" title="3"></span>"#,
+        r#"<span class="b a c" id="id" data-key="z" title="1"></span>"#,
+    );
+    for _ in 0..3 {
+        assert_eq!(render(&sorted), expected);
+    }
+}
+
+#[test]
+fn sse_data_frames() {
+    use maud::{sse_data, PreEscaped};
+    assert_eq!(
+        sse_data(html! { p { "<hi>" } }),
+        "data: <p>&lt;hi&gt;</p>\n\n"
+    );
+    assert_eq!(
+        sse_data(PreEscaped("a\r\nb\rc\n\nd\n")),
+        "data: a\ndata: b\ndata: c\ndata: \ndata: d\ndata: \n\n"
+    );
+    assert_eq!(sse_data(html! {}), "data: \n\n");
+}
+
+#[test]
+fn rendered_len_matches_render() {
+    use maud::{display, html_static, PreEscaped, Render, DOCTYPE};
+    use std::{borrow::Cow, rc::Rc};
+
+    fn check(value: impl Render) {
+        assert_eq!(value.rendered_len(), value.render().0.len());
+    }
+
+    check(r#"<a href="x">Tom & "Jerry"</a>"#);
+    check(String::from("héllo <wörld>"));
+    check(Cow::Borrowed("&&"));
+    check(-1234567i64);
+    check(u128::MAX);
+    check(0u8);
+    check('<');
+    check(2.5f64);
+    check(format_args!("{} < {}", 1, 2));
+    check(display("a & b"));
+    check(vec!["<", ">", "x"]);
+    check(Box::new("<b>") as Box<dyn Render>);
+    check(Rc::new(PreEscaped("<b>")));
+    check(DOCTYPE);
+    check(html! { p.x title="<>" { "Hi & " (42) } });
+    check(html_static! { p.x title="<>" { "Hi & bye" } });
+}
+
+#[test]
+fn rendered_len_without_rendering() {
+    use maud::Render;
+
+    struct Counted<'a>(&'a std::cell::Cell<usize>);
+
+    impl Render for Counted<'_> {
+        fn render_to(&self, w: &mut String) {
+            self.0.set(self.0.get() + 1);
+            w.push_str("<br>");
+        }
+
+        fn rendered_len(&self) -> usize {
+            4
+        }
+    }
+
+    let renders = std::cell::Cell::new(0);
+    let items = [Counted(&renders), Counted(&renders)];
+    assert_eq!(items.rendered_len(), 8);
+    assert_eq!(items[..].rendered_len(), 8);
+    assert_eq!(renders.get(), 0);
+}
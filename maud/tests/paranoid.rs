@@ -0,0 +1,27 @@
+#![cfg(all(feature = "paranoid", debug_assertions))]
+
+use maud::{html, PreEscaped};
+
+#[test]
+fn whitespace_is_allowed() {
+    let result = html! { (PreEscaped("<pre>\ta\r\n\x0Cb</pre>")) };
+    assert_eq!(result.into_string(), "<pre>\ta\r\n\x0Cb</pre>");
+}
+
+#[test]
+#[should_panic = "control character '\\0'"]
+fn nul_is_rejected() {
+    let _ = html! { p { (PreEscaped("a\0b")) } };
+}
+
+#[test]
+#[should_panic = "control character '\\u{7f}'"]
+fn delete_is_rejected() {
+    let _ = html! { p { (PreEscaped(String::from("\x7F"))) } };
+}
+
+#[test]
+#[should_panic = "control character '\\u{1b}'"]
+fn raw_attributes_are_checked() {
+    let _ = html! { p (PreEscaped("data-x=\"\x1B\"")) {} };
+}
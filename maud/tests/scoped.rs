@@ -0,0 +1,82 @@
+#![cfg(feature = "scoped-css")]
+
+use maud::{html, scoped, ScopeIds};
+
+#[test]
+fn scopes_markup_and_styles() {
+    let mut ids = ScopeIds::new();
+    let card = scoped(
+        &mut ids,
+        ".card > h2, p.note:hover { color: red; }",
+        html! {
+            div.card { h2 { "Title" } br; p.note { "Text" } }
+        },
+    );
+    assert_eq!(
+        card.into_string(),
+        concat!(
+            "<style>.card > h2[data-maud-0], p.note:hover[data-maud-0] { color: red; }</style>",
+            r#"<div data-maud-0 class="card"><h2 data-maud-0>Title</h2><br data-maud-0>"#,
+            r#"<p data-maud-0 class="note">Text</p></div>"#,
+        )
+    );
+}
+
+#[test]
+fn each_call_gets_a_new_scope() {
+    let mut ids = ScopeIds::new();
+    let first = scoped(&mut ids, "", html! { p { "One" } });
+    let second = scoped(&mut ids, "", html! { p { "Two" } });
+    assert_eq!(first.into_string(), "<p data-maud-0>One</p>");
+    assert_eq!(second.into_string(), "<p data-maud-1>Two</p>");
+}
+
+#[test]
+fn pseudo_elements() {
+    let css = "a::before, a:after, q:first-letter, a:not(.x::y) { content: '' }";
+    let markup = scoped(&mut ScopeIds::new(), css, "");
+    assert_eq!(
+        markup.into_string(),
+        concat!(
+            "<style>a[data-maud-0]::before, a[data-maud-0]:after, ",
+            "q[data-maud-0]:first-letter, a:not(.x::y)[data-maud-0] { content: '' }</style>",
+        )
+    );
+}
+
+#[test]
+fn at_rules() {
+    let css = concat!(
+        "@import url(a.css);",
+        "@media (min-width: 600px) { nav a { float: left } }",
+        "@keyframes spin { from { rotate: 0 } to { rotate: 1turn } }",
+        "/* {comment} */ [title='{,}'] { color: blue }",
+    );
+    let markup = scoped(&mut ScopeIds::new(), css, "");
+    assert_eq!(
+        markup.into_string(),
+        concat!(
+            "<style>@import url(a.css);",
+            "@media (min-width: 600px) { nav a[data-maud-0] { float: left } }",
+            "@keyframes spin { from { rotate: 0 } to { rotate: 1turn } }",
+            "/* {comment} */ [title='{,}'][data-maud-0] { color: blue }</style>",
+        )
+    );
+}
+
+#[test]
+fn raw_text_and_comments_are_skipped() {
+    let markup = html! {
+        (maud::PreEscaped(r#"<!-- <b> --><a title="x>y">"#))
+        script { (maud::PreEscaped("if (a <b) {}")) }
+        i { "x" }
+        (maud::PreEscaped("</a>"))
+    };
+    assert_eq!(
+        scoped(&mut ScopeIds::new(), "", markup).into_string(),
+        concat!(
+            r#"<!-- <b> --><a data-maud-0 title="x>y">"#,
+            "<script data-maud-0>if (a <b) {}</script><i data-maud-0>x</i></a>",
+        )
+    );
+}
@@ -37,6 +37,28 @@ fn attributes() {
     );
 }
 
+#[test]
+fn attribute_conditional_expressions() {
+    for &(active, expected) in &[(true, "on"), (false, "off")] {
+        let result = html! {
+            button class=(if active { "on" } else { "off" }) {}
+        };
+        assert_eq!(
+            result.into_string(),
+            format!(r#"<button class="{}"></button>"#, expected)
+        );
+    }
+    for &(count, expected) in &[(0, "none"), (1, "one"), (5, "many")] {
+        let result = html! {
+            span data-count=(match count { 0 => "none", 1 => "one", _ => "many" }) {}
+        };
+        assert_eq!(
+            result.into_string(),
+            format!(r#"<span data-count="{}"></span>"#, expected)
+        );
+    }
+}
+
 #[test]
 fn class_shorthand() {
     let pinkie_class = "pinkie";
@@ -100,6 +122,34 @@ fn structs() {
     assert_eq!(result.into_string(), "Name: Pinkie Pie. Rating: 1");
 }
 
+#[test]
+fn cow_strings() {
+    use std::borrow::Cow;
+    let borrowed: Cow<str> = Cow::Borrowed("<Applejack>");
+    let owned: Cow<str> = Cow::Owned("<Rarity>".to_owned());
+    let result = html! { (borrowed) " & " (owned) };
+    assert_eq!(
+        result.into_string(),
+        "&lt;Applejack&gt; &amp; &lt;Rarity&gt;"
+    );
+}
+
+#[test]
+fn rcdata_end_tags_are_escaped() {
+    let evil = "</textarea><script>alert(1)</script>";
+    let result = html! {
+        title { (evil) }
+        textarea { (evil) }
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "<title>&lt;/textarea&gt;&lt;script&gt;alert(1)&lt;/script&gt;</title>",
+            "<textarea>&lt;/textarea&gt;&lt;script&gt;alert(1)&lt;/script&gt;</textarea>",
+        )
+    );
+}
+
 #[test]
 fn tuple_accessors() {
     let a = ("ducks", "geese");
@@ -130,3 +180,95 @@ fn expression_grouping() {
     let result = html! { (1 + 1) };
     assert_eq!(result.into_string(), "2");
 }
+
+#[test]
+fn expression_body() {
+    fn render_body(name: &str) -> maud::Markup {
+        html! { h2 { (name) } p { "Hello!" } }
+    }
+    let name = "Rarity";
+    let result = html! {
+        div.card = render_body(name);
+        span = name.len();
+        b = "<bold>";
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<div class="card"><h2>Rarity</h2><p>Hello!</p></div>"#,
+            "<span>6</span>",
+            "<b>&lt;bold&gt;</b>",
+        )
+    );
+}
+
+#[test]
+fn expression_body_with_blocks() {
+    let names = ["a", "b"];
+    let result = html! {
+        p = { let n = names.len(); n * 2 };
+        p = names.iter().map(|name| { name.to_uppercase() }).collect::<Vec<_>>().join(",");
+    };
+    assert_eq!(result.into_string(), "<p>4</p><p>A,B</p>");
+}
+
+#[test]
+fn literal_splices() {
+    let result = html! {
+        p title=("\"quoted\"") { ("<b>") ('&') (("nested")) (r"raw\n") }
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<p title="&quot;quoted&quot;">&lt;b&gt;&amp;nestedraw\n</p>"#
+    );
+}
+
+#[test]
+fn raw_block() {
+    let body = String::from("<em>trusted</em>");
+    let markup = html! { b { "bold" } };
+    let count = 3;
+    let result = html! {
+        "<before>" (body)
+        @raw_block {
+            "<literal>" (body) (markup) (count) (format_args!("{}", "<a>"))
+            p title=(body) {}
+            @let inner = "<i>";
+            (inner)
+        }
+        (body)
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            "&lt;before&gt;&lt;em&gt;trusted&lt;/em&gt;",
+            "&lt;literal&gt;<em>trusted</em><b>bold</b>3<a>",
+            r#"<p title="<em>trusted</em>"></p><i>"#,
+            "&lt;em&gt;trusted&lt;/em&gt;",
+        )
+    );
+}
+
+#[test]
+fn raw_block_render_types() {
+    struct Greeting;
+    impl maud::Render for Greeting {
+        fn render_to(&self, buffer: &mut String) {
+            buffer.push_str("&lt;hi&gt;");
+        }
+    }
+    let result = html! { @raw_block { (Greeting) } };
+    assert_eq!(result.into_string(), "&lt;hi&gt;");
+}
+
+#[test]
+fn raw_block_literal_splices() {
+    let s = "<b>";
+    let result = html! {
+        @raw_block { ("<b>") (s) (('&')) "<text>" p title=("<q>") {} }
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<b><b>&&lt;text&gt;<p title="<q>"></p>"#
+    );
+}
@@ -0,0 +1,39 @@
+use maud::{html, html_static, PreEscaped};
+
+const FOOTER: PreEscaped<&str> = html_static! {
+    footer #footer .site-footer lang="en" hidden {
+        p { "Made with <3 & " a href="https://maud.lambda.xyz/" { "Maud" } }
+        br;
+    }
+};
+
+#[test]
+fn html_static_const() {
+    assert_eq!(
+        FOOTER.0,
+        concat!(
+            r#"<footer class="site-footer" id="footer" lang="en" hidden>"#,
+            r#"<p>Made with &lt;3 &amp; <a href="https://maud.lambda.xyz/">Maud</a></p>"#,
+            "<br></footer>",
+        ),
+    );
+}
+
+#[test]
+fn html_static_matches_html() {
+    let dynamic = html! { ul { li { "a" } li.b { "b" } } };
+    let fixed = html_static! { ul { li { "a" } li.b { "b" } } };
+    assert_eq!(fixed.0, dynamic.into_string());
+}
+
+#[test]
+fn html_static_splices_as_markup() {
+    let result = html! { main { (FOOTER) } };
+    assert!(result.into_string().starts_with("<main><footer"));
+}
+
+#[test]
+fn html_static_nested_blocks() {
+    let result = html_static! { p { "a" } { i { "b" } } @raw_block { b { "hi" } } };
+    assert_eq!(result.0, "<p>a</p><i>b</i><b>hi</b>");
+}
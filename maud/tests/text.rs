@@ -0,0 +1,171 @@
+use maud::{html, plural, plural_fmt};
+
+#[test]
+fn plural_counts() {
+    assert_eq!(plural(1, "item", "items").into_string(), "1 item");
+    assert_eq!(plural(0, "item", "items").into_string(), "0 items");
+    assert_eq!(plural(5u64, "item", "items").into_string(), "5 items");
+    assert_eq!(plural(-1, "degree", "degrees").into_string(), "-1 degree");
+    assert_eq!(plural(-3, "degree", "degrees").into_string(), "-3 degrees");
+    assert_eq!(plural(1.5, "cup", "cups").into_string(), "1.5 cups");
+    assert_eq!(plural(1.0, "cup", "cups").into_string(), "1 cup");
+}
+
+#[test]
+fn plural_escaping() {
+    let result = html! { p { (plural(2, "<b>", "<b>s")) } };
+    assert_eq!(result.into_string(), "<p>2 &lt;b&gt;s</p>");
+}
+
+#[test]
+fn plural_fmt_counts() {
+    assert_eq!(
+        plural_fmt(1, "{} new message", "{} new messages").into_string(),
+        "1 new message"
+    );
+    assert_eq!(
+        plural_fmt(7, "Only {} left", "Only {} left!").into_string(),
+        "Only 7 left!"
+    );
+    assert_eq!(
+        plural_fmt(2, "one", "{} & {} again").into_string(),
+        "2 &amp; 2 again"
+    );
+}
+
+#[test]
+fn highlight_matches() {
+    use maud::highlight;
+    assert_eq!(
+        highlight("Hello World", "wor").into_string(),
+        "Hello <mark>Wor</mark>ld"
+    );
+    assert_eq!(
+        highlight("banana", "ANA").into_string(),
+        "b<mark>ana</mark>na"
+    );
+    assert_eq!(
+        highlight("aaaa", "aa").into_string(),
+        "<mark>aa</mark><mark>aa</mark>"
+    );
+    assert_eq!(highlight("no match", "xyz").into_string(), "no match");
+    assert_eq!(
+        highlight("Ünïcödé ÜNÏ", "ünï").into_string(),
+        "<mark>Ünï</mark>cödé <mark>ÜNÏ</mark>"
+    );
+}
+
+#[test]
+fn highlight_escaping() {
+    use maud::highlight;
+    assert_eq!(
+        highlight("<script> & <b>", "<b>").into_string(),
+        "&lt;script&gt; &amp; <mark>&lt;b&gt;</mark>"
+    );
+    assert_eq!(highlight("<a>", "").into_string(), "&lt;a&gt;");
+    assert_eq!(
+        highlight("a&amp;b", "&amp;").into_string(),
+        "a<mark>&amp;amp;</mark>b"
+    );
+}
+
+#[test]
+fn strip_tags_text_content() {
+    use maud::strip_tags;
+    assert_eq!(
+        strip_tags(html! { p { "Hello " b { "world" } } }),
+        "Hello world"
+    );
+    let markup = html! {
+        article.post data-x="a > b" {
+            h1 { "Tom & Jerry's \"show\"" }
+            img src="x.png" alt="<no>";
+            p { "1 < 2" br; "2 > 1" }
+        }
+    };
+    assert_eq!(strip_tags(&markup), "Tom & Jerry's \"show\"1 < 22 > 1");
+}
+
+#[test]
+fn strip_tags_skips_comments_and_raw_text() {
+    use maud::{strip_tags, PreEscaped};
+    let markup = PreEscaped(concat!(
+        "<!-- note -->a<script>if (x < y) {}</script>",
+        "b<STYLE>p > a {}</Style>c<p title='x>y'>d</p>",
+    ));
+    assert_eq!(strip_tags(markup), "abcd");
+}
+
+#[test]
+fn strip_tags_references() {
+    use maud::{strip_tags, PreEscaped};
+    assert_eq!(
+        strip_tags(PreEscaped("&#65;&#x42;&apos;&nbsp;&copy;&#xZZ;&amp")),
+        "AB'\u{A0}&copy;&#xZZ;&amp"
+    );
+    // Unescaped text is escaped before stripping, so it comes back unchanged
+    assert_eq!(strip_tags("<b>&amp;</b>"), "<b>&amp;</b>");
+}
+
+#[test]
+fn wbr_every_boundaries() {
+    use maud::wbr_every;
+    assert_eq!(wbr_every("abcdefgh", 4).into_string(), "abcd<wbr>efgh");
+    assert_eq!(
+        wbr_every("abcdefghi", 4).into_string(),
+        "abcd<wbr>efgh<wbr>i"
+    );
+    assert_eq!(wbr_every("abc", 4).into_string(), "abc");
+    assert_eq!(wbr_every("abc", 0).into_string(), "abc");
+    assert_eq!(wbr_every("", 2).into_string(), "");
+    assert_eq!(wbr_every("héllö", 2).into_string(), "hé<wbr>ll<wbr>ö");
+}
+
+#[test]
+fn wbr_every_escaping() {
+    use maud::wbr_every;
+    assert_eq!(
+        wbr_every("a<b>&c", 3).into_string(),
+        "a&lt;b<wbr>&gt;&amp;c"
+    );
+}
+
+#[test]
+fn trim_trailing() {
+    use maud::trim_trailing;
+    let items = ["a", "b", "c"];
+    let markup = html! { @for item in &items { (item) " | " } };
+    assert_eq!(trim_trailing(markup, "|").into_string(), "a | b | c ");
+    assert_eq!(trim_trailing("a, b,\n", ",").into_string(), "a, b");
+    assert_eq!(trim_trailing("a, b", ",").into_string(), "a, b");
+    assert_eq!(trim_trailing("a,", "").into_string(), "a,");
+    assert_eq!(trim_trailing("", ",").into_string(), "");
+}
+
+#[test]
+fn trim_trailing_escaped_separator() {
+    use maud::trim_trailing;
+    assert_eq!(
+        trim_trailing("salt & pepper &", "&").into_string(),
+        "salt &amp; pepper "
+    );
+    assert_eq!(
+        trim_trailing(html! { "fish " b { "&" } }, "&").into_string(),
+        "fish <b>&amp;</b>"
+    );
+}
+
+#[test]
+fn trim_trailing_keeps_references_whole() {
+    use maud::trim_trailing;
+    assert_eq!(trim_trailing(html! { "a &" }, ";").into_string(), "a &amp;");
+    assert_eq!(trim_trailing(html! { "a >" }, "t;").into_string(), "a &gt;");
+    assert_eq!(
+        trim_trailing(html! { "a &amp;;" }, ";").into_string(),
+        "a &amp;amp;"
+    );
+    assert_eq!(
+        trim_trailing(html! { "a & b;" }, ";").into_string(),
+        "a &amp; b"
+    );
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "validation")]
+
+use maud::{html, validate_html, PreEscaped, ValidationError, ValidationErrorKind};
+
+fn error(offset: usize, kind: ValidationErrorKind) -> ValidationError {
+    ValidationError { offset, kind }
+}
+
+#[test]
+fn valid_markup() {
+    let result = html! {
+        (maud::DOCTYPE)
+        html {
+            head { meta charset="utf-8"; style { "p > a { color: red }" } }
+            body {
+                div.outer { p { "Hello " b { "world" } br; } }
+                span { a href="/" { "home" } }
+                script { (PreEscaped("if (a < b) { x('</div>'); }")) }
+            }
+        }
+    };
+    assert_eq!(validate_html(&result), Ok(()));
+}
+
+#[test]
+fn invalid_nesting() {
+    let result = html! { p { "a" div { "b" } } span { em { ul {} } } };
+    assert_eq!(
+        validate_html(result),
+        Err(vec![
+            error(
+                4,
+                ValidationErrorKind::InvalidNesting {
+                    child: "div".into(),
+                    parent: "p".into(),
+                }
+            ),
+            error(
+                30,
+                ValidationErrorKind::InvalidNesting {
+                    child: "ul".into(),
+                    parent: "em".into(),
+                }
+            ),
+        ])
+    );
+}
+
+#[test]
+fn paragraph_in_paragraph() {
+    let errors = validate_html(html! { p { p { "nested" } } }).unwrap_err();
+    assert_eq!(
+        errors[0].to_string(),
+        "`<p>` can't be placed inside `<p>` (at byte 3)"
+    );
+}
+
+#[test]
+fn unclosed_and_unexpected_tags() {
+    let result = PreEscaped("<div><span>a</div></b><ul>");
+    assert_eq!(
+        validate_html(result),
+        Err(vec![
+            error(
+                5,
+                ValidationErrorKind::UnclosedElement {
+                    name: "span".into()
+                }
+            ),
+            error(
+                18,
+                ValidationErrorKind::UnexpectedEndTag { name: "b".into() }
+            ),
+            error(
+                22,
+                ValidationErrorKind::UnclosedElement { name: "ul".into() }
+            ),
+        ])
+    );
+}
+
+#[test]
+fn malformed_tag() {
+    let result = PreEscaped(r#"<p>text</p><img src=">"#);
+    assert_eq!(
+        validate_html(result),
+        Err(vec![error(11, ValidationErrorKind::MalformedTag)])
+    );
+}
+
+#[test]
+fn minified_output_is_rejected() {
+    let result = maud::html_minify! { ul { li { "a" } li { "b" } } };
+    assert!(validate_html(result).is_err());
+}
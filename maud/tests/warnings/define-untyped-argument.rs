@@ -0,0 +1,10 @@
+use maud::html;
+
+fn main() {
+    html! {
+        @define greeting(name) {
+            p { "Hi " (name) }
+        }
+        @use greeting("Sam")
+    };
+}
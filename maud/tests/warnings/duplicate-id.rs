@@ -0,0 +1,18 @@
+use maud::html;
+
+fn main() {
+    let flag = true;
+    html! {
+        div #x {}
+        @if flag {
+            p #y {}
+        } @else {
+            p #y {}
+        }
+        span id="x" {}
+        i #y {}
+        @debug_only { b #z {} }
+        @release_only { b #z {} }
+        @debug_only { em #z {} }
+    };
+}
@@ -0,0 +1,8 @@
+use maud::html;
+
+fn main() {
+    html! {
+        h1 { "Hello" }
+        p = "world"
+    };
+}
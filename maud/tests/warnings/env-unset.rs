@@ -0,0 +1,11 @@
+use maud::html;
+
+fn main() {
+    html! {
+        p { @env("MAUD_SURELY_UNSET_VARIABLE") }
+    };
+
+    html! {
+        p { @env(ASSET_URL) }
+    };
+}
@@ -0,0 +1,6 @@
+#[derive(maud::MaudForm)]
+struct Order {
+    items: Vec<String>,
+}
+
+fn main() {}
@@ -4,4 +4,19 @@ fn main() {
     html! {
         if {}
     };
+
+    let _items = [1, 2, 3];
+    html! {
+        for item in &_items {
+            (item)
+        }
+    };
+
+    html! {
+        @if true {
+            "yes"
+        } else {
+            "no"
+        }
+    };
 }
@@ -0,0 +1,10 @@
+use maud::html;
+
+fn main() {
+    html! {
+        @itemprop("name") "Cupcake"
+    };
+    html! {
+        @itemprop("name") span itemprop="other" {}
+    };
+}
@@ -0,0 +1,9 @@
+use maud::html;
+
+struct Pony;
+
+fn main() {
+    html! {
+        p { "Hello, " (Pony) "!" }
+    };
+}
@@ -0,0 +1,12 @@
+use maud::html_static;
+
+fn main() {
+    let _user_input = "<script>";
+    let _enabled = true;
+    html_static! {
+        div.card[_enabled] {
+            (_user_input)
+            @if _enabled { "on" }
+        }
+    };
+}
@@ -0,0 +1,10 @@
+use maud::html;
+
+fn main() {
+    let x = Some(1);
+    html! {
+        @unless let Some(_) = x {
+            "none"
+        }
+    };
+}
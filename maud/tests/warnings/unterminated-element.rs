@@ -0,0 +1,10 @@
+use maud::html;
+
+fn main() {
+    html! {
+        div {
+            p.intro { "Hello" }
+            section #main
+        }
+    };
+}
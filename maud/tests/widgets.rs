@@ -0,0 +1,419 @@
+use maud::{html, FieldIds, PageMeta, Politeness, PreEscaped};
+
+#[test]
+fn breadcrumbs() {
+    let crumbs = [("Home", "/"), ("<Docs>", "/docs?a=1&b=2")];
+    let result = maud::breadcrumbs(&crumbs, html! { svg.icon {} });
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<nav aria-label="Breadcrumb"><ol>"#,
+            r#"<li><a href="/">Home</a><span aria-hidden="true"><svg class="icon"></svg></span></li>"#,
+            r#"<li aria-current="page">&lt;Docs&gt;</li>"#,
+            "</ol></nav>",
+        )
+    );
+}
+
+#[test]
+fn breadcrumbs_single_and_empty() {
+    let single = [("Home".to_owned(), "/".to_owned())];
+    assert_eq!(
+        maud::breadcrumbs(&single, PreEscaped("&rsaquo;")).into_string(),
+        r#"<nav aria-label="Breadcrumb"><ol><li aria-current="page">Home</li></ol></nav>"#
+    );
+    let empty: [(&str, &str); 0] = [];
+    assert_eq!(
+        maud::breadcrumbs(&empty, "/").into_string(),
+        r#"<nav aria-label="Breadcrumb"><ol></ol></nav>"#
+    );
+}
+
+#[test]
+fn breadcrumbs_escape_href() {
+    let crumbs = [("a", r#"/"onmouseover="x"#), ("b", "/b")];
+    let result = maud::breadcrumbs(&crumbs, ">");
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<nav aria-label="Breadcrumb"><ol>"#,
+            r#"<li><a href="/&quot;onmouseover=&quot;x">a</a><span aria-hidden="true">&gt;</span></li>"#,
+            r#"<li aria-current="page">b</li>"#,
+            "</ol></nav>",
+        )
+    );
+}
+
+fn page_url(page: usize) -> String {
+    format!("?page={}", page)
+}
+
+#[test]
+fn pagination_window() {
+    let result = html! { (maud::pagination(5, 10, page_url).window(1)) };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<nav aria-label="Pagination"><ul>"#,
+            r#"<li><a href="?page=4" rel="prev">Previous</a></li>"#,
+            r#"<li><a href="?page=4">4</a></li>"#,
+            r#"<li><a href="?page=5" aria-current="page">5</a></li>"#,
+            r#"<li><a href="?page=6">6</a></li>"#,
+            r#"<li><a href="?page=6" rel="next">Next</a></li>"#,
+            "</ul></nav>",
+        )
+    );
+}
+
+#[test]
+fn pagination_last_page() {
+    let result = html! { (maud::pagination(3, 3, page_url)) };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<nav aria-label="Pagination"><ul>"#,
+            r#"<li><a href="?page=2" rel="prev">Previous</a></li>"#,
+            r#"<li><a href="?page=1">1</a></li>"#,
+            r#"<li><a href="?page=2">2</a></li>"#,
+            r#"<li><a href="?page=3" aria-current="page">3</a></li>"#,
+            r#"<li><span aria-disabled="true">Next</span></li>"#,
+            "</ul></nav>",
+        )
+    );
+}
+
+#[test]
+fn pagination_single_page() {
+    assert_eq!(
+        html! { (maud::pagination(1, 1, page_url)) }.into_string(),
+        ""
+    );
+    assert_eq!(
+        html! { (maud::pagination(1, 0, page_url)) }.into_string(),
+        ""
+    );
+}
+
+#[test]
+fn pagination_escapes_urls() {
+    let result =
+        html! { (maud::pagination(1, 2, |page| format!("/?a=1&page={}", page)).window(0)) };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<nav aria-label="Pagination"><ul>"#,
+            r#"<li><span aria-disabled="true">Previous</span></li>"#,
+            r#"<li><a href="/?a=1&amp;page=1" aria-current="page">1</a></li>"#,
+            r#"<li><a href="/?a=1&amp;page=2" rel="next">Next</a></li>"#,
+            "</ul></nav>",
+        )
+    );
+}
+
+#[test]
+fn icons() {
+    let icons = maud::IconSet::new("/sprite.svg?v=1&x=2");
+    let result = html! {
+        button { (icons.icon("trash")) "Delete" }
+        (maud::icon(r#"x"onload="alert(1)"#))
+    };
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<button><svg><use href="/sprite.svg?v=1&amp;x=2#trash"></use></svg>Delete</button>"#,
+            r##"<svg><use href="#x&quot;onload=&quot;alert(1)"></use></svg>"##,
+        )
+    );
+}
+
+#[test]
+fn progress() {
+    let result = html! { (maud::progress(0.25, 1)) };
+    assert_eq!(
+        result.into_string(),
+        r#"<progress value="0.25" max="1" aria-valuenow="0.25" aria-valuemin="0" aria-valuemax="1"></progress>"#
+    );
+}
+
+#[test]
+fn progress_clamped() {
+    let over = html! { (maud::progress(12, 10).fallback()) };
+    assert_eq!(
+        over.into_string(),
+        r#"<progress value="10" max="10" aria-valuenow="10" aria-valuemin="0" aria-valuemax="10">100%</progress>"#
+    );
+    let under = html! { (maud::progress(-3, 10).fallback()) };
+    assert_eq!(
+        under.into_string(),
+        r#"<progress value="0" max="10" aria-valuenow="0" aria-valuemin="0" aria-valuemax="10">0%</progress>"#
+    );
+}
+
+#[test]
+fn progress_fallback_rounds() {
+    let result = html! { (maud::progress(2, 3).fallback()) };
+    assert!(result.into_string().ends_with(">67%</progress>"));
+}
+
+#[test]
+fn progress_indeterminate() {
+    for markup in [
+        maud::progress(1, 0),
+        maud::progress(1, -5),
+        maud::progress(f64::NAN, 10),
+        maud::progress(1, f64::INFINITY),
+    ] {
+        let result = html! { (markup.fallback()) };
+        assert_eq!(result.into_string(), "<progress></progress>");
+    }
+}
+
+#[test]
+fn meta_tags() {
+    let tags = [
+        ("description", r#"Rarity's "fabulous" <shop>"#),
+        ("OG:Image", "/a.png?w=1&h=2"),
+        ("article:author", "Rarity"),
+        ("twitter:card", "summary"),
+        ("og", "not a property"),
+    ];
+    assert_eq!(
+        maud::meta_tags(&tags).into_string(),
+        concat!(
+            r#"<meta name="description" content="Rarity's &quot;fabulous&quot; &lt;shop&gt;">"#,
+            r#"<meta property="OG:Image" content="/a.png?w=1&amp;h=2">"#,
+            r#"<meta property="article:author" content="Rarity">"#,
+            r#"<meta name="twitter:card" content="summary">"#,
+            r#"<meta name="og" content="not a property">"#,
+        )
+    );
+    let empty: [(String, String); 0] = [];
+    assert_eq!(maud::meta_tags(&empty).into_string(), "");
+}
+
+#[test]
+fn page_head() {
+    let meta = PageMeta {
+        title: "Maud's <Rocks>",
+        description: Some(r#"Rocks & "boulders""#),
+        canonical_url: Some("https://example.com/rocks?a=1&b=2"),
+        og_image: Some("https://example.com/rock.png"),
+        site_name: Some("Pie Family Rock Farm"),
+    };
+    assert_eq!(
+        maud::page_head(&meta).into_string(),
+        concat!(
+            "<title>Maud's &lt;Rocks&gt;</title>",
+            r#"<meta name="description" content="Rocks &amp; &quot;boulders&quot;">"#,
+            r#"<link rel="canonical" href="https://example.com/rocks?a=1&amp;b=2">"#,
+            r#"<meta property="og:title" content="Maud's &lt;Rocks&gt;">"#,
+            r#"<meta property="og:description" content="Rocks &amp; &quot;boulders&quot;">"#,
+            r#"<meta property="og:url" content="https://example.com/rocks?a=1&amp;b=2">"#,
+            r#"<meta property="og:image" content="https://example.com/rock.png">"#,
+            r#"<meta property="og:site_name" content="Pie Family Rock Farm">"#,
+            r#"<meta name="twitter:card" content="summary_large_image">"#,
+        )
+    );
+}
+
+#[test]
+fn page_head_title_only() {
+    let meta = PageMeta {
+        title: "Home",
+        ..PageMeta::default()
+    };
+    assert_eq!(
+        maud::page_head(&meta).into_string(),
+        concat!(
+            "<title>Home</title>",
+            r#"<meta property="og:title" content="Home">"#,
+            r#"<meta name="twitter:card" content="summary">"#,
+        )
+    );
+}
+
+#[test]
+fn preload_links() {
+    let assets = [
+        ("/main.css?v=1&min", "style"),
+        ("/logo.png", " Image "),
+        ("/font.woff2", "FONT"),
+        ("/data.json", "fetch"),
+        (r#"/"quoted".js"#, "script"),
+    ];
+    assert_eq!(
+        maud::preload_links(&assets).into_string(),
+        concat!(
+            r#"<link rel="preload" href="/main.css?v=1&amp;min" as="style">"#,
+            r#"<link rel="preload" href="/logo.png" as="image">"#,
+            r#"<link rel="preload" href="/font.woff2" as="font" crossorigin>"#,
+            r#"<link rel="preload" href="/&quot;quoted&quot;.js" as="script">"#,
+        )
+    );
+    let empty: [(&str, &str); 0] = [];
+    assert_eq!(maud::preload_links(&empty).into_string(), "");
+}
+
+#[test]
+fn meta_refresh() {
+    assert_eq!(
+        maud::meta_refresh(0, "https://example.com/").into_string(),
+        r#"<meta http-equiv="refresh" content="0; url='https://example.com/'">"#
+    );
+    assert_eq!(
+        maud::meta_refresh(3, " /a;b,c?q=\"x\"&r='y' ").into_string(),
+        r#"<meta http-equiv="refresh" content="3; url='/a;b,c?q=%22x%22&amp;r=%27y%27'">"#
+    );
+}
+
+#[test]
+fn kbd_combo() {
+    let result = html! { p { "Press " (maud::kbd_combo(&["Ctrl", "<", "&"])) } };
+    assert_eq!(
+        result.into_string(),
+        "<p>Press <kbd>Ctrl</kbd>+<kbd>&lt;</kbd>+<kbd>&amp;</kbd></p>"
+    );
+    let single = html! { (maud::kbd_combo(&["Esc"])) };
+    assert_eq!(single.into_string(), "<kbd>Esc</kbd>");
+    let empty: [&str; 0] = [];
+    assert_eq!(html! { (maud::kbd_combo(&empty)) }.into_string(), "");
+}
+
+#[test]
+fn kbd_combo_separator() {
+    let keys = ["Alt", "F4"];
+    let plain = html! { (maud::kbd_combo(&keys).separator(" + ")) };
+    assert_eq!(plain.into_string(), "<kbd>Alt</kbd> + <kbd>F4</kbd>");
+    let raw = html! { (maud::kbd_combo(&keys).separator(PreEscaped("&thinsp;"))) };
+    assert_eq!(raw.into_string(), "<kbd>Alt</kbd>&thinsp;<kbd>F4</kbd>");
+    let escaped = html! { (maud::kbd_combo(&keys).separator("<")) };
+    assert_eq!(escaped.into_string(), "<kbd>Alt</kbd>&lt;<kbd>F4</kbd>");
+}
+
+#[test]
+fn skip_link() {
+    let result = html! { (maud::skip_link("content")) };
+    assert_eq!(
+        result.into_string(),
+        r##"<a class="maud-skip-link" href="#content">Skip to main content</a>"##
+    );
+    let result = html! {
+        (maud::skip_link("#\"main\"").text(html! { "Skip " b { "<ahead>" } }))
+    };
+    assert_eq!(
+        result.into_string(),
+        r##"<a class="maud-skip-link" href="#&quot;main&quot;">Skip <b>&lt;ahead&gt;</b></a>"##
+    );
+}
+
+#[test]
+fn with_noscript() {
+    let result = html! {
+        (maud::with_noscript(html! { button.js-only { "Copy" } }, "<Select the text>"))
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<button class="js-only">Copy</button><noscript>&lt;Select the text&gt;</noscript>"#
+    );
+}
+
+#[test]
+fn avatar_initials() {
+    assert_eq!(
+        maud::avatar(None, "Jane Doe").into_string(),
+        r#"<div role="img" aria-label="Jane Doe">JD</div>"#
+    );
+    assert_eq!(
+        maud::avatar(None, "  mary ann <evans>  ").into_string(),
+        r#"<div role="img" aria-label="  mary ann &lt;evans&gt;  ">M&lt;</div>"#
+    );
+    assert_eq!(
+        maud::avatar(None, "élodie").into_string(),
+        r#"<div role="img" aria-label="élodie">É</div>"#
+    );
+    assert_eq!(
+        maud::avatar(None, "").into_string(),
+        r#"<div role="img" aria-label=""></div>"#
+    );
+}
+
+#[test]
+fn avatar_image() {
+    assert_eq!(
+        maud::avatar(Some("/a.png?size=32&round"), "<Jane>").into_string(),
+        r#"<img src="/a.png?size=32&amp;round" alt="&lt;Jane&gt;">"#
+    );
+}
+
+#[test]
+fn details() {
+    let result = html! {
+        (maud::details("<Spoilers>", html! { p { "It was the butler." } }, false))
+    };
+    assert_eq!(
+        result.into_string(),
+        "<details><summary>&lt;Spoilers&gt;</summary><p>It was the butler.</p></details>"
+    );
+}
+
+#[test]
+fn details_open() {
+    let result = maud::details(html! { b { "Details" } }, "Shown", true);
+    assert_eq!(
+        result.into_string(),
+        "<details open><summary><b>Details</b></summary>Shown</details>"
+    );
+}
+
+#[test]
+fn tabs() {
+    let mut ids = FieldIds::new("<x>");
+    let _ = maud::labeled_input(&mut ids, "Name", &[("name", "name")]);
+    let result = maud::tabs(
+        &mut ids,
+        &[("<One>", "first"), ("Two", "second"), ("Three", "third")],
+    );
+    assert_eq!(
+        result.into_string(),
+        concat!(
+            r#"<div role="tablist">"#,
+            r#"<button type="button" role="tab" id="&lt;x&gt;-1-tab" aria-controls="&lt;x&gt;-1-panel" aria-selected="true">&lt;One&gt;</button>"#,
+            r#"<button type="button" role="tab" id="&lt;x&gt;-2-tab" aria-controls="&lt;x&gt;-2-panel" aria-selected="false" tabindex="-1">Two</button>"#,
+            r#"<button type="button" role="tab" id="&lt;x&gt;-3-tab" aria-controls="&lt;x&gt;-3-panel" aria-selected="false" tabindex="-1">Three</button>"#,
+            "</div>",
+            r#"<div role="tabpanel" id="&lt;x&gt;-1-panel" aria-labelledby="&lt;x&gt;-1-tab" tabindex="0">first</div>"#,
+            r#"<div role="tabpanel" id="&lt;x&gt;-2-panel" aria-labelledby="&lt;x&gt;-2-tab" tabindex="0" hidden>second</div>"#,
+            r#"<div role="tabpanel" id="&lt;x&gt;-3-panel" aria-labelledby="&lt;x&gt;-3-tab" tabindex="0" hidden>third</div>"#,
+        )
+    );
+}
+
+#[test]
+fn tabs_empty() {
+    let empty: [(&str, &str); 0] = [];
+    let result = maud::tabs(&mut FieldIds::new("t"), &empty);
+    assert_eq!(result.into_string(), r#"<div role="tablist"></div>"#);
+}
+
+#[test]
+fn live_region() {
+    let result = html! {
+        (maud::live_region(Politeness::Assertive, "<3 new messages>"))
+    };
+    assert_eq!(
+        result.into_string(),
+        r#"<div aria-live="assertive" aria-atomic="true">&lt;3 new messages&gt;</div>"#
+    );
+}
+
+#[test]
+fn politeness_from_str_lossy() {
+    assert_eq!(Politeness::from_str_lossy("off"), Politeness::Off);
+    assert_eq!(
+        Politeness::from_str_lossy(" Assertive "),
+        Politeness::Assertive
+    );
+    assert_eq!(Politeness::from_str_lossy("polite"), Politeness::Polite);
+    assert_eq!(Politeness::from_str_lossy("loud"), Politeness::Polite);
+    assert_eq!(Politeness::from_str_lossy(""), Politeness::Polite);
+}
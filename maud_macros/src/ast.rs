@@ -1,4 +1,4 @@
-use proc_macro2::{TokenStream, TokenTree};
+use proc_macro2::{Ident, TokenStream, TokenTree};
 use proc_macro_error::SpanRange;
 use syn::Lit;
 
@@ -27,7 +27,10 @@ pub enum Markup {
     },
     Let {
         at_span: SpanRange,
+        /// The `let` statement. If there's an `else_block`, then this
+        /// excludes the `else` and the trailing semicolon.
         tokens: TokenStream,
+        else_block: Option<Block>,
     },
     Special {
         segments: Vec<Special>,
@@ -38,6 +41,42 @@ pub enum Markup {
         arms: Vec<MatchArm>,
         arms_span: SpanRange,
     },
+    /// `@slot(slots, name) { ... }`, which renders its body into `slots`
+    /// rather than the output.
+    Slot {
+        at_span: SpanRange,
+        slots: TokenStream,
+        name: TokenStream,
+        body: Block,
+    },
+    /// `@define name(args) { ... }`, which binds a reusable fragment to
+    /// `name`.
+    Define {
+        at_span: SpanRange,
+        name: Ident,
+        /// Each argument, as `pattern: Type`.
+        params: Vec<TokenStream>,
+        body: Block,
+    },
+    /// `@use name(args)`, which renders a fragment from `@define`.
+    Use {
+        at_span: SpanRange,
+        name: Ident,
+        args: TokenStream,
+        args_span: SpanRange,
+    },
+    /// `@raw_block { ... }`, whose splices are written without escaping.
+    RawBlock {
+        at_span: SpanRange,
+        body: Block,
+    },
+    /// An item that tells Cargo to rebuild the template when a file or
+    /// environment variable that it was built from changes, such as
+    /// `const _: &[u8] = include_bytes!("...");`.
+    Dependency {
+        item: TokenStream,
+        span: SpanRange,
+    },
 }
 
 impl Markup {
@@ -57,11 +96,29 @@ impl Markup {
             Markup::Let {
                 at_span,
                 ref tokens,
-            } => at_span.join_range(span_tokens(tokens.clone())),
+                ref else_block,
+            } => {
+                let span = at_span.join_range(span_tokens(tokens.clone()));
+                match else_block {
+                    Some(else_block) => span.join_range(else_block.span()),
+                    None => span,
+                }
+            }
             Markup::Special { ref segments } => join_ranges(segments.iter().map(Special::span)),
             Markup::Match {
                 at_span, arms_span, ..
             } => at_span.join_range(arms_span),
+            Markup::Slot {
+                at_span, ref body, ..
+            } => at_span.join_range(body.span()),
+            Markup::Define {
+                at_span, ref body, ..
+            } => at_span.join_range(body.span()),
+            Markup::Use {
+                at_span, args_span, ..
+            } => at_span.join_range(args_span),
+            Markup::RawBlock { at_span, ref body } => at_span.join_range(body.span()),
+            Markup::Dependency { span, .. } => span,
         }
     }
 }
@@ -72,6 +129,9 @@ pub enum Attr {
         dot_span: SpanRange,
         name: Markup,
         toggler: Option<Toggler>,
+        /// The class to add instead when the toggler is false, as in
+        /// `.active[cond]:inactive`.
+        else_name: Option<Markup>,
     },
     Id {
         hash_span: SpanRange,
@@ -80,6 +140,20 @@ pub enum Attr {
     Named {
         named_attr: NamedAttr,
     },
+    /// A pre-built string of attributes, written as `(expr)`.
+    Raw {
+        expr: TokenStream,
+        span: SpanRange,
+    },
+    /// A group of attributes that share one condition, written as
+    /// `@toggle(cond) { ... } @else { ... }`.
+    Toggle {
+        at_span: SpanRange,
+        cond: TokenStream,
+        then_attrs: Vec<Attr>,
+        else_attrs: Vec<Attr>,
+        span: SpanRange,
+    },
 }
 
 impl Attr {
@@ -89,14 +163,17 @@ impl Attr {
                 dot_span,
                 ref name,
                 ref toggler,
+                ref else_name,
             } => {
                 let name_span = name.span();
-                let dot_name_span = dot_span.join_range(name_span);
+                let mut span = dot_span.join_range(name_span);
                 if let Some(toggler) = toggler {
-                    dot_name_span.join_range(toggler.cond_span)
-                } else {
-                    dot_name_span
+                    span = span.join_range(toggler.cond_span);
+                }
+                if let Some(else_name) = else_name {
+                    span = span.join_range(else_name.span());
                 }
+                span
             }
             Attr::Id {
                 hash_span,
@@ -106,6 +183,8 @@ impl Attr {
                 hash_span.join_range(name_span)
             }
             Attr::Named { ref named_attr } => named_attr.span(),
+            Attr::Raw { span, .. } => span,
+            Attr::Toggle { at_span, span, .. } => at_span.join_range(span),
         }
     }
 }
@@ -170,16 +249,36 @@ impl NamedAttr {
 
 #[derive(Debug)]
 pub enum AttrType {
-    Normal { value: Markup },
-    Optional { toggler: Toggler },
-    Empty { toggler: Option<Toggler> },
+    Normal {
+        value: Markup,
+    },
+    /// A value that's only rendered if the toggler is on, as in
+    /// `value=(v)[show]`.
+    Toggled {
+        value: Markup,
+        toggler: Toggler,
+    },
+    /// A value that's only rendered if it's `Some`, as in `value=[v]`. With
+    /// a fallback, as in `value=[v ?? "none"]`, the fallback is rendered
+    /// instead of `None`.
+    Optional {
+        toggler: Toggler,
+        fallback: Option<TokenStream>,
+    },
+    Empty {
+        toggler: Option<Toggler>,
+    },
 }
 
 impl AttrType {
     fn span(&self) -> Option<SpanRange> {
         match *self {
             AttrType::Normal { ref value } => Some(value.span()),
-            AttrType::Optional { ref toggler } => Some(toggler.span()),
+            AttrType::Toggled {
+                ref value,
+                ref toggler,
+            } => Some(value.span().join_range(toggler.span())),
+            AttrType::Optional { ref toggler, .. } => Some(toggler.span()),
             AttrType::Empty { ref toggler } => toggler.as_ref().map(Toggler::span),
         }
     }
@@ -14,9 +14,26 @@ pub fn escape_to_string(input: &str, output: &mut String) {
     }
 }
 
+/// Like `escape_to_string`, but for attribute values delimited by single
+/// quotes. This escapes `'` instead of `"`.
+///
+/// The runtime crate has no equivalent, as splices are escaped in full and
+/// then have their single quotes escaped afterward.
+pub fn escape_single_quoted_to_string(input: &str, output: &mut String) {
+    for b in input.bytes() {
+        match b {
+            b'&' => output.push_str("&amp;"),
+            b'<' => output.push_str("&lt;"),
+            b'>' => output.push_str("&gt;"),
+            b'\'' => output.push_str("&#39;"),
+            _ => unsafe { output.as_mut_vec().push(b) },
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use super::escape_to_string;
+    use super::{escape_single_quoted_to_string, escape_to_string};
 
     #[test]
     fn it_works() {
@@ -24,4 +41,11 @@ mod test {
         escape_to_string("<script>launchMissiles()</script>", &mut s);
         assert_eq!(s, "&lt;script&gt;launchMissiles()&lt;/script&gt;");
     }
+
+    #[test]
+    fn single_quoted() {
+        let mut s = String::new();
+        escape_single_quoted_to_string(r#"it's "fine" & <ok>"#, &mut s);
+        assert_eq!(s, r#"it&#39;s "fine" &amp; &lt;ok&gt;"#);
+    }
 }
@@ -0,0 +1,133 @@
+use proc_macro2::{Literal, TokenStream};
+use proc_macro_error::{abort, abort_call_site};
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, GenericArgument, PathArguments, Type};
+
+/// The kinds of field that `#[derive(MaudForm)]` knows how to render.
+enum FieldKind {
+    Text,
+    Number,
+    Decimal,
+    Checkbox,
+}
+
+pub fn derive(input: DeriveInput) -> TokenStream {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => abort!(
+                input.ident,
+                "`MaudForm` can only be derived for structs with named fields"
+            ),
+        },
+        _ => abort_call_site!("`MaudForm` can only be derived for structs"),
+    };
+    let fields = fields.iter().map(|field| {
+        let ident = field.ident.as_ref().expect("named field");
+        let name = ident.to_string();
+        let name = name.strip_prefix("r#").unwrap_or(&name);
+        let label = Literal::string(&label(name));
+        let name = Literal::string(name);
+        let (ty, optional) = match option_inner(&field.ty) {
+            Some(ty) => (ty, true),
+            None => (&field.ty, false),
+        };
+        let kind = field_kind(ty);
+        let input = match (kind, optional) {
+            (FieldKind::Checkbox, false) => quote! {
+                input #(#name) name=(#name) type="checkbox" checked[self.#ident];
+            },
+            (FieldKind::Checkbox, true) => quote! {
+                input #(#name) name=(#name) type="checkbox" checked[self.#ident == Some(true)];
+            },
+            (kind, optional) => {
+                let type_attrs = match kind {
+                    FieldKind::Text => quote!(type="text"),
+                    FieldKind::Number => quote!(type="number"),
+                    FieldKind::Decimal => quote!(type="number" step="any"),
+                    FieldKind::Checkbox => unreachable!(),
+                };
+                if optional {
+                    quote!(input #(#name) name=(#name) #type_attrs value=[self.#ident.as_ref()];)
+                } else {
+                    quote!(input #(#name) name=(#name) #type_attrs value=(self.#ident);)
+                }
+            }
+        };
+        quote! {
+            div {
+                label for=(#name) { (#label) }
+                #input
+            }
+        }
+    });
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    quote! {
+        impl #impl_generics #ident #ty_generics #where_clause {
+            /// Renders a labeled input for each field of this struct.
+            pub fn render_fields(&self) -> maud::Markup {
+                maud::html! {
+                    #(#fields)*
+                }
+            }
+        }
+    }
+}
+
+/// Turns a field name like `first_name` into a label like `First name`.
+fn label(name: &str) -> String {
+    let words = name.split('_').filter(|word| !word.is_empty());
+    let mut label = words.collect::<Vec<_>>().join(" ");
+    if let Some(first) = label.get(..1) {
+        label = first.to_uppercase() + &label[1..];
+    }
+    label
+}
+
+/// If `ty` is `Option<T>`, returns `T`.
+fn option_inner(ty: &Type) -> Option<&Type> {
+    let Type::Path(path) = ty else {
+        return None;
+    };
+    let segment = path.path.segments.last()?;
+    if segment.ident != "Option" {
+        return None;
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    match args.args.first()? {
+        GenericArgument::Type(ty) => Some(ty),
+        _ => None,
+    }
+}
+
+fn field_kind(ty: &Type) -> FieldKind {
+    let name = match ty {
+        Type::Path(path) => path
+            .path
+            .segments
+            .last()
+            .map(|segment| segment.ident.to_string()),
+        Type::Reference(reference) => match &*reference.elem {
+            Type::Path(path) if path.path.is_ident("str") => Some("str".to_owned()),
+            _ => None,
+        },
+        _ => None,
+    };
+    match name.as_deref() {
+        Some("String" | "str") => FieldKind::Text,
+        Some(
+            "i8" | "i16" | "i32" | "i64" | "i128" | "isize" | "u8" | "u16" | "u32" | "u64" | "u128"
+            | "usize",
+        ) => FieldKind::Number,
+        Some("f32" | "f64") => FieldKind::Decimal,
+        Some("bool") => FieldKind::Checkbox,
+        _ => abort!(
+            ty,
+            "unsupported field type for `MaudForm`";
+            help = "supported types are `String`, `&str`, `bool`, integers, floats, and `Option`s of these"
+        ),
+    }
+}
@@ -1,34 +1,336 @@
 use proc_macro2::{Delimiter, Group, Ident, Literal, Span, TokenStream, TokenTree};
 use proc_macro_error::SpanRange;
-use quote::quote;
+use quote::{quote, quote_spanned};
+use syn::{Expr, Lifetime, Lit};
 
 use crate::{ast::*, escape};
 
-pub fn generate(markups: Vec<Markup>, output_ident: TokenTree) -> TokenStream {
-    let mut build = Builder::new(output_ident.clone());
-    Generator::new(output_ident).markups(markups, &mut build);
-    build.finish()
+pub fn generate(markups: Vec<Markup>, output_ident: TokenTree, options: Options) -> TokenStream {
+    let mut build = Builder::new(output_ident.clone(), options);
+    #[cfg(feature = "lints")]
+    build.push_tokens(crate::lint::lints(&markups));
+    Generator::new(output_ident.clone(), options).markups(markups, &mut build);
+    intern_statics(build.finish(), &output_ident)
 }
 
+/// Generates a template that has no splices or control structures, as
+/// checked by [`parse_static`](crate::parse::parse_static), returning its
+/// output and the items that track the files and environment variables that
+/// it was built from.
+pub fn generate_static(markups: Vec<Markup>, options: Options) -> (String, TokenStream) {
+    let mut dependencies = TokenStream::new();
+    collect_dependencies(&markups, &mut dependencies);
+    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+    let mut build = Builder::new(output_ident.clone(), options);
+    Generator::new(output_ident.clone(), options).markups(markups, &mut build);
+    let mut output = String::new();
+    collect_static(build.finish(), &output_ident, &mut output);
+    (output, dependencies)
+}
+
+/// Appends the item of each [`Markup::Dependency`] in `markups` to
+/// `dependencies`.
+fn collect_dependencies(markups: &[Markup], dependencies: &mut TokenStream) {
+    for markup in markups {
+        match markup {
+            Markup::Block(block)
+            | Markup::RawBlock { body: block, .. }
+            | Markup::Element {
+                body: ElementBody::Block { block },
+                ..
+            } => collect_dependencies(&block.markups, dependencies),
+            Markup::Dependency { item, .. } => dependencies.extend(item.clone()),
+            _ => {}
+        }
+    }
+}
+
+/// Appends each static string that `stmts` writes to `output`, including
+/// those in nested blocks such as `@raw_block`. Any other statements, such as
+/// those that track nesting, are dropped.
+fn collect_static(stmts: TokenStream, output_ident: &TokenTree, output: &mut String) {
+    let stmts = stmts.into_iter().collect::<Vec<_>>();
+    for i in 0..stmts.len() {
+        if let Some(literal) = static_push_arg(&stmts, i, output_ident) {
+            if let Lit::Str(string) = Lit::new(literal) {
+                output.push_str(&string.value());
+            }
+        } else if let TokenTree::Group(group) = &stmts[i] {
+            if group.delimiter() == Delimiter::Brace {
+                collect_static(group.stream(), output_ident, output);
+            }
+        }
+    }
+}
+
+/// Moves each static string that's written more than once into a `const`,
+/// so that the generated code only holds one copy of it.
+fn intern_statics(stmts: TokenStream, output_ident: &TokenTree) -> TokenStream {
+    let mut statics = Vec::new();
+    count_statics(stmts.clone(), output_ident, &mut statics);
+    let repeated = statics
+        .into_iter()
+        .filter(|&(_, _, count)| count > 1)
+        .map(|(key, literal, _)| (key, literal))
+        .collect::<Vec<_>>();
+    if repeated.is_empty() {
+        return stmts;
+    }
+    let mut result = TokenStream::new();
+    for (index, (_, literal)) in repeated.iter().enumerate() {
+        let name = static_ident(index);
+        result.extend(quote!(const #name: &str = #literal;));
+    }
+    result.extend(replace_statics(stmts, output_ident, &repeated));
+    result
+}
+
+/// Counts how often each static string is written, keyed by its literal.
+fn count_statics(
+    stmts: TokenStream,
+    output_ident: &TokenTree,
+    statics: &mut Vec<(String, Literal, usize)>,
+) {
+    let tokens = stmts.into_iter().collect::<Vec<_>>();
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(literal) = static_push_arg(&tokens, i, output_ident) {
+            let key = literal.to_string();
+            match statics.iter_mut().find(|(other, _, _)| *other == key) {
+                Some((_, _, count)) => *count += 1,
+                None => statics.push((key, literal, 1)),
+            }
+        } else if let TokenTree::Group(group) = token {
+            count_statics(group.stream(), output_ident, statics);
+        }
+    }
+}
+
+/// Replaces each static string in `repeated` with the `const` that holds it.
+fn replace_statics(
+    stmts: TokenStream,
+    output_ident: &TokenTree,
+    repeated: &[(String, Literal)],
+) -> TokenStream {
+    let tokens = stmts.into_iter().collect::<Vec<_>>();
+    let mut result = Vec::with_capacity(tokens.len());
+    for (i, token) in tokens.iter().enumerate() {
+        if let Some(literal) = static_push_arg(&tokens, i, output_ident) {
+            let key = literal.to_string();
+            if let Some(index) = repeated.iter().position(|(other, _)| *other == key) {
+                let name = static_ident(index);
+                let group = Group::new(Delimiter::Parenthesis, quote!(#name));
+                result.push(TokenTree::Group(group));
+                continue;
+            }
+        } else if let TokenTree::Group(group) = token {
+            let mut new_group = Group::new(
+                group.delimiter(),
+                replace_statics(group.stream(), output_ident, repeated),
+            );
+            new_group.set_span(group.span());
+            result.push(TokenTree::Group(new_group));
+            continue;
+        }
+        result.push(token.clone());
+    }
+    result.into_iter().collect()
+}
+
+/// If `tokens[i]` is the argument list of a `push_str` call, made by
+/// [`Builder::cut`] with a string literal, returns that literal.
+fn static_push_arg(tokens: &[TokenTree], i: usize, output_ident: &TokenTree) -> Option<Literal> {
+    let TokenTree::Group(group) = &tokens[i] else {
+        return None;
+    };
+    if i < 3 || group.delimiter() != Delimiter::Parenthesis {
+        return None;
+    }
+    let is_output = matches!(
+        &tokens[i - 3],
+        TokenTree::Ident(ident)
+            if ident.to_string().starts_with("__maud_")
+                || matches!(output_ident, TokenTree::Ident(output) if output == ident)
+    );
+    let is_push_str = matches!(&tokens[i - 2], TokenTree::Punct(punct) if punct.as_char() == '.')
+        && matches!(&tokens[i - 1], TokenTree::Ident(ident) if ident == "push_str");
+    if !is_output || !is_push_str {
+        return None;
+    }
+    let mut args = group.stream().into_iter();
+    match (args.next(), args.next()) {
+        (Some(TokenTree::Literal(literal)), None) if literal.to_string().starts_with('"') => {
+            Some(literal)
+        }
+        _ => None,
+    }
+}
+
+fn static_ident(index: usize) -> Ident {
+    Ident::new(&format!("__MAUD_STATIC_{}", index), Span::mixed_site())
+}
+
+/// Settings that differ between the `html!` macro variants.
+#[derive(Clone, Copy, Default)]
+pub struct Options {
+    pub quote_style: QuoteStyle,
+    /// Whether the output buffer is something other than an
+    /// `alloc::string::String`. If so, splices are rendered into a temporary
+    /// `String` first, then copied over with `push_str`.
+    pub custom_buffer: bool,
+    /// Whether to leave out end tags that the HTML spec allows to be
+    /// omitted. See [`end_tag_omissible`] for the rules.
+    pub omit_end_tags: bool,
+    /// Whether to add `loading="lazy"` and `decoding="async"` to `img` and
+    /// `iframe` elements that don't set them already.
+    pub lazy_media: bool,
+}
+
+/// The delimiter placed around attribute values.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteStyle {
+    #[default]
+    Double,
+    Single,
+}
+
+impl QuoteStyle {
+    fn as_str(self) -> &'static str {
+        match self {
+            QuoteStyle::Double => "\"",
+            QuoteStyle::Single => "'",
+        }
+    }
+}
+
+#[derive(Clone)]
 struct Generator {
     output_ident: TokenTree,
+    options: Options,
+    /// Whether we're generating the value of an attribute.
+    in_attribute: bool,
+    /// The number of `@let ... else` blocks we're inside, used to keep their
+    /// labels distinct.
+    let_else_depth: usize,
+    /// The name of the element whose body we're generating, if the markups
+    /// being generated are the whole of that body.
+    parent: Option<String>,
+    /// Whether we're inside a `<script>`, `<style>`, `<title>` or
+    /// `<textarea>` element, where a comment would be read as text.
+    #[cfg(feature = "audit")]
+    in_raw_text: bool,
+    /// Whether we're inside a `@raw_block`, where splices aren't escaped.
+    raw: bool,
 }
 
 impl Generator {
-    fn new(output_ident: TokenTree) -> Generator {
-        Generator { output_ident }
+    fn new(output_ident: TokenTree, options: Options) -> Generator {
+        Generator {
+            output_ident,
+            options,
+            in_attribute: false,
+            let_else_depth: 0,
+            parent: None,
+            #[cfg(feature = "audit")]
+            in_raw_text: false,
+            raw: false,
+        }
     }
 
     fn builder(&self) -> Builder {
-        Builder::new(self.output_ident.clone())
+        let mut build = Builder::new(self.output_ident.clone(), self.options);
+        build.in_attribute = self.in_attribute;
+        build
+    }
+
+    fn attribute_value(&self) -> Generator {
+        Generator {
+            in_attribute: true,
+            ..self.clone()
+        }
+    }
+
+    /// Returns a generator for markups that may be followed by more content
+    /// in the same parent element.
+    fn nested(&self) -> Generator {
+        Generator {
+            parent: None,
+            ..self.clone()
+        }
     }
 
     fn markups(&self, markups: Vec<Markup>, build: &mut Builder) {
-        for markup in markups {
-            self.markup(markup, build);
+        let omit_end_tags = (0..markups.len())
+            .map(|i| {
+                self.options.omit_end_tags
+                    && match &markups[i] {
+                        Markup::Element { name, .. } => end_tag_omissible(
+                            &name_to_string(name.clone()),
+                            markups.get(i + 1),
+                            self.parent.as_deref(),
+                        ),
+                        _ => false,
+                    }
+            })
+            .collect::<Vec<_>>();
+        let mut markups = markups.into_iter().zip(omit_end_tags);
+        while let Some((markup, omit_end_tag)) = markups.next() {
+            match markup {
+                Markup::Let {
+                    tokens,
+                    else_block: Some(else_block),
+                    ..
+                } => {
+                    let rest = markups.map(|(markup, _)| markup).collect();
+                    self.let_else(tokens, else_block, rest, build);
+                    return;
+                }
+                Markup::Element { name, attrs, body } => {
+                    self.element(name, attrs, body, omit_end_tag, build);
+                }
+                markup => self.markup(markup, build),
+            }
         }
     }
 
+    /// Generates `@let PAT = EXPR else { ... };`, followed by the `rest` of
+    /// the enclosing block.
+    ///
+    /// If the pattern doesn't match, then the `else` body is rendered and
+    /// the rest of the block is skipped.
+    fn let_else(
+        &self,
+        tokens: TokenStream,
+        else_block: Block,
+        rest: Vec<Markup>,
+        build: &mut Builder,
+    ) {
+        let label = Lifetime::new(
+            &format!("'__maud_let_else_{}", self.let_else_depth),
+            Span::mixed_site(),
+        );
+        let inner = Generator {
+            let_else_depth: self.let_else_depth + 1,
+            ..self.clone()
+        };
+        let else_body = {
+            let mut build = inner.builder();
+            inner.markups(else_block.markups, &mut build);
+            build.finish()
+        };
+        let rest = {
+            let mut build = inner.builder();
+            inner.markups(rest, &mut build);
+            build.finish()
+        };
+        build.push_tokens(quote!(#label: {
+            #tokens else {
+                #else_body
+                break #label;
+            };
+            #rest
+        }));
+    }
+
     fn markup(&self, markup: Markup, build: &mut Builder) {
         match markup {
             Markup::ParseError { .. } => {}
@@ -38,7 +340,7 @@ impl Generator {
             }) => {
                 if markups
                     .iter()
-                    .any(|markup| matches!(*markup, Markup::Let { .. }))
+                    .any(|markup| matches!(*markup, Markup::Let { .. } | Markup::Define { .. }))
                 {
                     self.block(
                         Block {
@@ -48,20 +350,48 @@ impl Generator {
                         build,
                     );
                 } else {
-                    self.markups(markups, build);
+                    self.nested().markups(markups, build);
                 }
             }
             Markup::Literal { content, .. } => build.push_escaped(&content),
             Markup::Symbol { symbol } => self.name(symbol, build),
-            Markup::Splice { expr, .. } => self.splice(expr, build),
-            Markup::Element { name, attrs, body } => self.element(name, attrs, body, build),
+            Markup::Splice { expr, outer_span } => match literal_splice(&expr) {
+                // Inside `@raw_block`, a splice isn't escaped even if it's a
+                // literal
+                Some(content) if self.raw => build.push_str(&content),
+                Some(content) => build.push_escaped(&content),
+                None => {
+                    #[cfg(feature = "audit")]
+                    self.audit_comment(outer_span, build);
+                    self.splice(expr, outer_span.collapse(), build);
+                }
+            },
+            Markup::Element { name, attrs, body } => {
+                self.element(name, attrs, body, false, build);
+            }
             Markup::Let { tokens, .. } => build.push_tokens(tokens),
-            Markup::Special { segments } => {
+            Markup::Dependency { item, .. } => build.push_item(item),
+            Markup::Special { mut segments } => {
+                hoist_shared_splices(&mut segments, build);
                 for Special { head, body, .. } in segments {
                     build.push_tokens(head);
                     self.block(body, build);
                 }
             }
+            Markup::Slot {
+                slots, name, body, ..
+            } => self.slot(slots, name, body, build),
+            Markup::Define {
+                name, params, body, ..
+            } => self.define(name, params, body, build),
+            Markup::Use { name, args, .. } => self.use_define(name, args, build),
+            Markup::RawBlock { body, .. } => {
+                let inner = Generator {
+                    raw: true,
+                    ..self.clone()
+                };
+                inner.block(body, build);
+            }
             Markup::Match {
                 head,
                 arms,
@@ -93,7 +423,7 @@ impl Generator {
     ) {
         let block = {
             let mut build = self.builder();
-            self.markups(markups, &mut build);
+            self.nested().markups(markups, &mut build);
             build.finish()
         };
         let mut block = TokenTree::Group(Group::new(Delimiter::Brace, block));
@@ -101,21 +431,214 @@ impl Generator {
         build.push_tokens(TokenStream::from(block));
     }
 
-    fn splice(&self, expr: TokenStream, build: &mut Builder) {
+    /// Generates a `@slot`, by rendering its body into a fresh `String` and
+    /// appending that to the slot.
+    fn slot(&self, slots: TokenStream, name: TokenStream, body: Block, build: &mut Builder) {
+        let slot_ident = TokenTree::Ident(Ident::new("__maud_slot_output", Span::mixed_site()));
+        let inner = Generator {
+            output_ident: slot_ident.clone(),
+            options: Options {
+                custom_buffer: false,
+                ..self.options
+            },
+            ..self.nested()
+        };
+        let body = {
+            let mut build = inner.builder();
+            inner.markups(body.markups, &mut build);
+            build.finish()
+        };
+        build.push_tokens(quote!({
+            let mut #slot_ident = maud::macro_private::String::new();
+            #body
+            (#slots).append(#name, maud::PreEscaped(#slot_ident));
+        }));
+    }
+
+    /// Generates a `@define`, as a closure that takes the output buffer
+    /// followed by the arguments.
+    fn define(&self, name: Ident, params: Vec<TokenStream>, body: Block, build: &mut Builder) {
+        let define_ident = Ident::new("__maud_define_output", Span::mixed_site());
+        // Dereferencing the buffer means it can be reborrowed with `&mut`,
+        // without declaring the argument as `mut`
+        let output_ident =
+            TokenTree::Group(Group::new(Delimiter::Parenthesis, quote!(*#define_ident)));
+        let inner = Generator {
+            output_ident,
+            options: Options {
+                custom_buffer: false,
+                ..self.options
+            },
+            ..self.nested()
+        };
+        let body = {
+            let mut build = inner.builder();
+            inner.markups(body.markups, &mut build);
+            build.finish()
+        };
+        build.push_tokens(quote!(
+            let #name = |#define_ident: &mut maud::macro_private::String, #(#params),*| {
+                #body
+            };
+        ));
+    }
+
+    /// Generates a `@use`, by calling the closure from its `@define`.
+    fn use_define(&self, name: Ident, args: TokenStream, build: &mut Builder) {
         let output_ident = self.output_ident.clone();
-        build.push_tokens(quote!(maud::macro_private::render_to!(&(#expr), &mut #output_ident);));
+        if self.options.custom_buffer {
+            let scratch = Ident::new("__maud_scratch", Span::mixed_site());
+            build.push_tokens(quote!({
+                let mut #scratch = maud::macro_private::String::new();
+                #name(&mut #scratch, #args);
+                #output_ident.push_str(&#scratch);
+            }));
+        } else {
+            build.push_tokens(quote!(#name(&mut #output_ident, #args);));
+        }
+    }
+
+    /// Generates a comment giving the location of a splice, so that
+    /// reviewers can see where dynamic data enters the page. The comment is
+    /// only written once it's turned on with `maud::set_audit`.
+    ///
+    /// Comments would change the meaning of attribute values and of raw text
+    /// and escapable raw text elements, such as `<script>` and `<title>`, so
+    /// none are added there.
+    #[cfg(feature = "audit")]
+    fn audit_comment(&self, span: SpanRange, build: &mut Builder) {
+        if self.in_attribute || self.in_raw_text {
+            return;
+        }
+        let output_ident = self.output_ident.clone();
+        // Spanning `file!()` and `line!()` at the splice makes them point to
+        // the splice, rather than to the `html!` call
+        let location = quote_spanned!(span.first=> concat!(
+            "<!-- splice: ", file!(), ":", line!(), " -->"
+        ));
+        build.push_tokens(quote!(
+            if maud::macro_private::audit_enabled() {
+                #output_ident.push_str(#location);
+            }
+        ));
     }
 
-    fn element(&self, name: TokenStream, attrs: Vec<Attr>, body: ElementBody, build: &mut Builder) {
+    /// Generates a splice of `expr`. The call is given `span`, the span of
+    /// the splice in the template, so that an error about the type of
+    /// `expr` points there.
+    fn splice(&self, expr: TokenStream, span: Span, build: &mut Builder) {
+        let output_ident = self.output_ident.clone();
+        let render_to = if self.raw {
+            quote_spanned!(span=> maud::macro_private::render_raw_to!)
+        } else if self.in_attribute && self.options.quote_style == QuoteStyle::Single {
+            quote_spanned!(span=> maud::macro_private::render_to_single_quoted!)
+        } else {
+            quote_spanned!(span=> maud::macro_private::render_to!)
+        };
+        if self.options.custom_buffer {
+            let scratch = Ident::new("__maud_scratch", Span::mixed_site());
+            let call = quote_spanned!(span=> #render_to(&(#expr), &mut #scratch););
+            build.push_tokens(quote!({
+                let mut #scratch = maud::macro_private::String::new();
+                #call
+                #output_ident.push_str(&#scratch);
+            }));
+        } else {
+            build.push_tokens(quote_spanned!(span=> #render_to(&(#expr), &mut #output_ident);));
+        }
+    }
+
+    fn element(
+        &self,
+        name: TokenStream,
+        attrs: Vec<Attr>,
+        body: ElementBody,
+        omit_end_tag: bool,
+        build: &mut Builder,
+    ) {
+        // Each `@toggle` condition is evaluated once, before the start tag
+        let attrs = attrs
+            .into_iter()
+            .enumerate()
+            .map(|(index, attr)| match attr {
+                Attr::Toggle {
+                    at_span,
+                    cond,
+                    then_attrs,
+                    else_attrs,
+                    span,
+                } => {
+                    let ident = Ident::new(&format!("__maud_toggle_{}", index), Span::mixed_site());
+                    let cond = toggle_cond(cond);
+                    build.push_tokens(quote!(let #ident: bool = #cond;));
+                    Attr::Toggle {
+                        at_span,
+                        cond: TokenStream::from(TokenTree::Ident(ident)),
+                        then_attrs,
+                        else_attrs,
+                        span,
+                    }
+                }
+                attr => attr,
+            })
+            .collect();
+        let (mut attrs, raw_attrs) = desugar_attrs(attrs);
+        // A raw attribute splice might set the same attributes, and can't be
+        // checked until run time, so it turns the defaults off
+        if self.options.lazy_media && raw_attrs.is_empty() {
+            attrs.extend(lazy_media_attrs(&name_to_string(name.clone()), &attrs));
+        }
         build.push_str("<");
         self.name(name.clone(), build);
         self.attrs(attrs, build);
+        for expr in raw_attrs {
+            self.raw_attrs(expr, build);
+        }
         build.push_str(">");
         if let ElementBody::Block { block } = body {
-            self.markups(block.markups, build);
-            build.push_str("</");
-            self.name(name, build);
-            build.push_str(">");
+            let name_string = name_to_string(name.clone());
+            let inner = Generator {
+                #[cfg(feature = "audit")]
+                in_raw_text: self.in_raw_text
+                    || matches!(
+                        name_string.to_ascii_lowercase().as_str(),
+                        "script" | "style" | "title" | "textarea"
+                    ),
+                parent: Some(name_string),
+                ..self.clone()
+            };
+            #[cfg(feature = "debug-nesting")]
+            build.push_tokens(quote!(
+                maud::macro_private::enter_element(concat!(file!(), ":", line!()));
+            ));
+            inner.markups(block.markups, build);
+            #[cfg(feature = "debug-nesting")]
+            build.push_tokens(quote!(
+                maud::macro_private::exit_element(concat!(file!(), ":", line!()));
+            ));
+            if !omit_end_tag {
+                build.push_str("</");
+                self.name(name, build);
+                build.push_str(">");
+            }
+        }
+    }
+
+    /// Generates a raw attribute splice, which writes the string in a
+    /// `PreEscaped` value verbatim, after a space.
+    fn raw_attrs(&self, expr: TokenStream, build: &mut Builder) {
+        let output_ident = self.output_ident.clone();
+        if self.options.custom_buffer {
+            let scratch = Ident::new("__maud_scratch", Span::mixed_site());
+            build.push_tokens(quote!({
+                let mut #scratch = maud::macro_private::String::new();
+                maud::macro_private::push_raw_attrs(&(#expr), &mut #scratch);
+                #output_ident.push_str(&#scratch);
+            }));
+        } else {
+            build.push_tokens(quote!(
+                maud::macro_private::push_raw_attrs(&(#expr), &mut #output_ident);
+            ));
         }
     }
 
@@ -123,30 +646,71 @@ impl Generator {
         build.push_escaped(&name_to_string(name));
     }
 
-    fn attrs(&self, attrs: Vec<Attr>, build: &mut Builder) {
-        for NamedAttr { name, attr_type } in desugar_attrs(attrs) {
+    fn attrs(&self, attrs: Vec<(NamedAttr, Option<TokenStream>)>, build: &mut Builder) {
+        let quote = self.options.quote_style.as_str();
+        for (NamedAttr { name, attr_type }, guard) in attrs {
+            if let Some(guard) = guard {
+                let body = {
+                    let mut build = self.builder();
+                    self.attrs(vec![(NamedAttr { name, attr_type }, None)], &mut build);
+                    build.finish()
+                };
+                build.push_tokens(quote!(if #guard { #body }));
+                continue;
+            }
             match attr_type {
                 AttrType::Normal { value } => {
                     build.push_str(" ");
                     self.name(name, build);
-                    build.push_str("=\"");
-                    self.markup(value, build);
-                    build.push_str("\"");
+                    build.push_str("=");
+                    build.push_str(quote);
+                    build.in_attribute = true;
+                    self.attribute_value().markup(value, build);
+                    build.in_attribute = false;
+                    build.push_str(quote);
                 }
-                AttrType::Optional {
+                AttrType::Toggled {
+                    value,
                     toggler: Toggler { cond, .. },
                 } => {
-                    let inner_value = quote!(inner_value);
+                    let cond = toggle_cond(cond);
                     let body = {
+                        let mut build = self.builder();
+                        let attr_type = AttrType::Normal { value };
+                        self.attrs(vec![(NamedAttr { name, attr_type }, None)], &mut build);
+                        build.finish()
+                    };
+                    build.push_tokens(quote!(if #cond { #body }));
+                }
+                AttrType::Optional {
+                    toggler: Toggler { cond, cond_span },
+                    fallback,
+                } => {
+                    let inner_value = quote!(inner_value);
+                    let attr_with = |value: TokenStream, span: Span| {
                         let mut build = self.builder();
                         build.push_str(" ");
-                        self.name(name, &mut build);
-                        build.push_str("=\"");
-                        self.splice(inner_value.clone(), &mut build);
-                        build.push_str("\"");
+                        self.name(name.clone(), &mut build);
+                        build.push_str("=");
+                        build.push_str(quote);
+                        self.attribute_value().splice(value, span, &mut build);
+                        build.push_str(quote);
                         build.finish()
                     };
-                    build.push_tokens(quote!(if let Some(#inner_value) = (#cond) { #body }));
+                    let body = attr_with(inner_value.clone(), cond_span.collapse());
+                    match fallback {
+                        Some(fallback) => {
+                            let fallback_span = span_tokens(fallback.clone()).collapse();
+                            let fallback_body = attr_with(fallback, fallback_span);
+                            build.push_tokens(quote!(
+                                if let Some(#inner_value) = (#cond) { #body } else { #fallback_body }
+                            ));
+                        }
+                        None => {
+                            build
+                                .push_tokens(quote!(if let Some(#inner_value) = (#cond) { #body }));
+                        }
+                    }
                 }
                 AttrType::Empty { toggler: None } => {
                     build.push_str(" ");
@@ -155,13 +719,14 @@ impl Generator {
                 AttrType::Empty {
                     toggler: Some(Toggler { cond, .. }),
                 } => {
+                    let cond = toggle_cond(cond);
                     let body = {
                         let mut build = self.builder();
                         build.push_str(" ");
                         self.name(name, &mut build);
                         build.finish()
                     };
-                    build.push_tokens(quote!(if (#cond) { #body }));
+                    build.push_tokens(quote!(if #cond { #body }));
                 }
             }
         }
@@ -170,36 +735,428 @@ impl Generator {
 
 ////////////////////////////////////////////////////////
 
-fn desugar_attrs(attrs: Vec<Attr>) -> Vec<NamedAttr> {
+/// Evaluates any splice that appears in every branch of an `@if` chain once,
+/// before the condition, rather than once per branch.
+///
+/// Splices count as the same if their tokens are. Only splices that read a
+/// variable or one of its fields, such as `(user.name)`, are hoisted, and only
+/// if nothing else in the chain mentions that variable: reading it earlier
+/// then can't change what's rendered. Splices that call functions are left
+/// where they are, as moving them could change the order of their side
+/// effects. Only chains that end in `@else` are changed, and only if their
+/// branches contain nothing but elements with static attributes, text and
+/// splices.
+fn hoist_shared_splices(segments: &mut [Special], build: &mut Builder) {
+    let is_if_else = segments.len() >= 2
+        && matches!(segments[0].head.clone().into_iter().next(), Some(TokenTree::Ident(ident)) if ident == "if")
+        && segments[segments.len() - 1].head.to_string() == "else"
+        && segments.iter().all(|segment| {
+            !segment
+                .head
+                .clone()
+                .into_iter()
+                .any(|token| matches!(token, TokenTree::Ident(ident) if ident == "let"))
+        });
+    if !is_if_else {
+        return;
+    }
+    let mut branches = Vec::new();
+    let mut others = segments
+        .iter()
+        .map(|segment| segment.head.clone())
+        .collect::<Vec<_>>();
+    for segment in segments.iter() {
+        let mut splices = Vec::new();
+        if !collect_splices(&segment.body.markups, &mut splices, &mut others) {
+            return;
+        }
+        branches.push(splices);
+    }
+    let (first, rest) = branches
+        .split_first()
+        .expect("segments should be non-empty");
+    let mut shared: Vec<&Shared> = Vec::new();
+    for splice in first {
+        if !shared.iter().any(|other| other.key == splice.key)
+            && rest
+                .iter()
+                .all(|splices| splices.iter().any(|other| other.key == splice.key))
+            && !others.iter().any(|tokens| mentions(tokens, &splice.root))
+        {
+            shared.push(splice);
+        }
+    }
+    for (index, Shared { key, expr, .. }) in shared.into_iter().enumerate() {
+        let ident = Ident::new(&format!("__maud_shared_{}", index), Span::mixed_site());
+        build.push_tokens(quote!(let #ident = &(#expr);));
+        for segment in segments.iter_mut() {
+            replace_splices(&mut segment.body.markups, key, &ident);
+        }
+    }
+}
+
+/// A splice that [`hoist_shared_splices`] may move out of a branch.
+struct Shared {
+    /// The splice's tokens, as a string.
+    key: String,
+    expr: TokenStream,
+    /// The variable that the splice reads.
+    root: Ident,
+}
+
+/// Collects the splices in `markups` that read a variable or its fields,
+/// keyed by their tokens. Other splices are added to `others`.
+///
+/// Returns `false` if `markups` contains anything else that runs code, such
+/// as a control structure or a dynamic attribute, in which case no splice can
+/// be moved out of it.
+fn collect_splices(
+    markups: &[Markup],
+    splices: &mut Vec<Shared>,
+    others: &mut Vec<TokenStream>,
+) -> bool {
+    for markup in markups {
+        let complete = match markup {
+            Markup::Block(block) => collect_splices(&block.markups, splices, others),
+            Markup::Element { attrs, body, .. } => {
+                attrs.iter().all(static_attr)
+                    && match body {
+                        ElementBody::Block { block } => {
+                            collect_splices(&block.markups, splices, others)
+                        }
+                        ElementBody::Void { .. } => true,
+                    }
+            }
+            Markup::Splice { expr, .. } => {
+                if literal_splice(expr).is_none() {
+                    match place_root(expr) {
+                        Some(root) => splices.push(Shared {
+                            key: expr.to_string(),
+                            expr: expr.clone(),
+                            root,
+                        }),
+                        None => others.push(expr.clone()),
+                    }
+                }
+                true
+            }
+            Markup::Literal { .. } | Markup::Symbol { .. } | Markup::Dependency { .. } => true,
+            _ => false,
+        };
+        if !complete {
+            return false;
+        }
+    }
+    true
+}
+
+/// Whether `attr` is rendered the same way every time.
+fn static_attr(attr: &Attr) -> bool {
+    match attr {
+        Attr::Class {
+            name: Markup::Literal { .. },
+            toggler: None,
+            else_name: None,
+            ..
+        }
+        | Attr::Id {
+            name: Markup::Literal { .. },
+            ..
+        } => true,
+        Attr::Named { named_attr } => matches!(
+            named_attr.attr_type,
+            AttrType::Normal {
+                value: Markup::Literal { .. }
+            } | AttrType::Empty { toggler: None }
+        ),
+        _ => false,
+    }
+}
+
+/// If `expr` is a variable, or a field of one, returns that variable.
+fn place_root(expr: &TokenStream) -> Option<Ident> {
+    fn root(expr: &Expr) -> Option<Ident> {
+        match expr {
+            Expr::Path(path) if path.qself.is_none() => {
+                Some(path.path.segments.first()?.ident.clone())
+            }
+            Expr::Field(field) => root(&field.base),
+            Expr::Paren(paren) => root(&paren.expr),
+            Expr::Group(group) => root(&group.expr),
+            _ => None,
+        }
+    }
+    root(&syn::parse2(expr.clone()).ok()?)
+}
+
+/// Whether `ident` appears anywhere in `tokens`.
+fn mentions(tokens: &TokenStream, ident: &Ident) -> bool {
+    tokens.clone().into_iter().any(|token| match token {
+        TokenTree::Ident(other) => other == *ident,
+        TokenTree::Group(group) => mentions(&group.stream(), ident),
+        _ => false,
+    })
+}
+
+/// Replaces each splice found by [`collect_splices`] whose tokens are `key`
+/// with a reference to the hoisted value in `ident`.
+fn replace_splices(markups: &mut [Markup], key: &str, ident: &Ident) {
+    for markup in markups {
+        match markup {
+            Markup::Block(block)
+            | Markup::Element {
+                body: ElementBody::Block { block },
+                ..
+            } => replace_splices(&mut block.markups, key, ident),
+            Markup::Splice { expr, .. } if expr.to_string() == key => *expr = quote!(*#ident),
+            _ => {}
+        }
+    }
+}
+
+/// If a splice is just a string or character literal, such as `("foo")`,
+/// returns its value so that it can be rendered at compile time.
+fn literal_splice(expr: &TokenStream) -> Option<String> {
+    let mut tokens = expr.clone().into_iter();
+    let token = tokens.next()?;
+    if tokens.next().is_some() {
+        return None;
+    }
+    match token {
+        TokenTree::Literal(literal) => match Lit::new(literal) {
+            Lit::Str(lit_str) => Some(lit_str.value()),
+            Lit::Char(lit_char) => Some(lit_char.value().to_string()),
+            _ => None,
+        },
+        TokenTree::Group(group)
+            if matches!(group.delimiter(), Delimiter::Parenthesis | Delimiter::None) =>
+        {
+            literal_splice(&group.stream())
+        }
+        _ => None,
+    }
+}
+
+/// Returns whether the end tag of an element called `name` can be left out,
+/// given the markup that follows it and the name of its parent element.
+///
+/// This implements a subset of the [optional tags] rules in the HTML spec:
+///
+/// * `li`: if followed by another `li`, or at the end of its parent.
+/// * `option`: if followed by another `option`, an `optgroup` or an `hr`, or
+///   at the end of its parent.
+/// * `td`: if followed by a `td` or `th`, or at the end of its parent.
+/// * `tr`: if followed by another `tr`, or at the end of its parent.
+/// * `p`: if followed by one of the block elements listed in the spec, such
+///   as `div`, `p` or `ul`; or at the end of its parent, unless that parent
+///   is an `a`, `audio`, `del`, `ins`, `map`, `noscript`, `video` or custom
+///   element.
+///
+/// Only what's known at compile time is considered. If the next sibling is
+/// anything other than an element, such as text, a splice or a control
+/// structure, then the end tag is kept. An element counts as being at the
+/// end of its parent only when it's the last thing in the parent's body,
+/// outside of any block or control structure.
+///
+/// [optional tags]: https://html.spec.whatwg.org/multipage/syntax.html#optional-tags
+fn end_tag_omissible(name: &str, next: Option<&Markup>, parent: Option<&str>) -> bool {
+    let next = match next {
+        Some(Markup::Element { name, .. }) => Some(name_to_string(name.clone())),
+        Some(_) => return false,
+        None => None,
+    };
+    let next = next.as_deref().map(str::to_ascii_lowercase);
+    let parent = parent.map(str::to_ascii_lowercase);
+    match (name.to_ascii_lowercase().as_str(), next.as_deref()) {
+        ("li", Some(next)) => next == "li",
+        ("option", Some(next)) => matches!(next, "option" | "optgroup" | "hr"),
+        ("td", Some(next)) => matches!(next, "td" | "th"),
+        ("tr", Some(next)) => next == "tr",
+        ("p", Some(next)) => matches!(
+            next,
+            "address"
+                | "article"
+                | "aside"
+                | "blockquote"
+                | "details"
+                | "dialog"
+                | "div"
+                | "dl"
+                | "fieldset"
+                | "figcaption"
+                | "figure"
+                | "footer"
+                | "form"
+                | "h1"
+                | "h2"
+                | "h3"
+                | "h4"
+                | "h5"
+                | "h6"
+                | "header"
+                | "hgroup"
+                | "hr"
+                | "main"
+                | "menu"
+                | "nav"
+                | "ol"
+                | "p"
+                | "pre"
+                | "search"
+                | "section"
+                | "table"
+                | "ul"
+        ),
+        ("li" | "option" | "td" | "tr", None) => parent.is_some(),
+        ("p", None) => match parent.as_deref() {
+            Some(parent) => {
+                !matches!(
+                    parent,
+                    "a" | "audio" | "del" | "ins" | "map" | "noscript" | "video"
+                ) && !parent.contains('-')
+            }
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+/// Returns the default attributes that `html_lazy!` adds to an element called
+/// `name`, leaving out any that are already in `attrs`.
+///
+/// `img` and `iframe` elements get `loading="lazy"` and `decoding="async"`.
+/// An attribute counts as present even if it's toggled, so `loading=[None]`
+/// leaves the attribute out entirely.
+fn lazy_media_attrs(
+    name: &str,
+    attrs: &[(NamedAttr, Option<TokenStream>)],
+) -> Vec<(NamedAttr, Option<TokenStream>)> {
+    if !matches!(name.to_ascii_lowercase().as_str(), "img" | "iframe") {
+        return Vec::new();
+    }
+    [("loading", "lazy"), ("decoding", "async")]
+        .into_iter()
+        .filter(|(attr_name, _)| {
+            !attrs
+                .iter()
+                .any(|(attr, _)| name_to_string(attr.name.clone()).eq_ignore_ascii_case(attr_name))
+        })
+        .map(|(attr_name, value)| {
+            let attr = NamedAttr {
+                name: TokenStream::from(TokenTree::Ident(Ident::new(attr_name, Span::call_site()))),
+                attr_type: AttrType::Normal {
+                    value: Markup::Literal {
+                        content: value.to_owned(),
+                        span: SpanRange::call_site(),
+                    },
+                },
+            };
+            (attr, None)
+        })
+        .collect()
+}
+
+////////////////////////////////////////////////////////
+
+/// Wraps the condition of a toggle, which may be a `bool` or an `Option`, in
+/// an expression of type `bool`.
+fn toggle_cond(cond: TokenStream) -> TokenStream {
+    quote!(maud::macro_private::Toggle::is_on(&(#cond)))
+}
+
+/// Splits `attrs` into named attributes and raw attribute expressions.
+///
+/// Attributes from a `@toggle` come with the condition that guards them,
+/// which must already be a plain `bool` variable.
+fn desugar_attrs(attrs: Vec<Attr>) -> (Vec<(NamedAttr, Option<TokenStream>)>, Vec<TokenStream>) {
     let mut classes_static = vec![];
     let mut classes_toggled = vec![];
     let mut ids = vec![];
     let mut named_attrs = vec![];
+    let mut raw_attrs = vec![];
     for attr in attrs {
         match attr {
             Attr::Class {
                 name,
-                toggler: Some(toggler),
+                toggler: Some(Toggler { cond, cond_span }),
+                else_name,
                 ..
-            } => classes_toggled.push((name, toggler)),
+            } => {
+                let toggler = Toggler {
+                    cond: toggle_cond(cond),
+                    cond_span,
+                };
+                classes_toggled.push((name, toggler, else_name));
+            }
             Attr::Class {
                 name,
                 toggler: None,
                 ..
             } => classes_static.push(name),
             Attr::Id { name, .. } => ids.push(name),
-            Attr::Named { named_attr } => named_attrs.push(named_attr),
+            Attr::Named { named_attr } => named_attrs.push((named_attr, None)),
+            Attr::Raw { expr, .. } => raw_attrs.push(expr),
+            Attr::Toggle {
+                at_span,
+                cond,
+                then_attrs,
+                else_attrs,
+                ..
+            } => {
+                for (attrs, guard) in [(then_attrs, quote!(#cond)), (else_attrs, quote!(!#cond))] {
+                    for attr in attrs {
+                        match attr {
+                            Attr::Class { name, toggler, .. } => {
+                                let cond = match toggler {
+                                    Some(Toggler { cond, .. }) => {
+                                        let cond = toggle_cond(cond);
+                                        quote!(#guard && #cond)
+                                    }
+                                    None => guard.clone(),
+                                };
+                                let toggler = Toggler {
+                                    cond,
+                                    cond_span: at_span,
+                                };
+                                classes_toggled.push((name, toggler, None));
+                            }
+                            Attr::Id { name, .. } => {
+                                let named_attr = NamedAttr {
+                                    name: TokenStream::from(TokenTree::Ident(Ident::new(
+                                        "id",
+                                        Span::call_site(),
+                                    ))),
+                                    attr_type: AttrType::Normal { value: name },
+                                };
+                                named_attrs.push((named_attr, Some(guard.clone())));
+                            }
+                            Attr::Named { named_attr } => {
+                                named_attrs.push((named_attr, Some(guard.clone())));
+                            }
+                            Attr::Raw { .. } | Attr::Toggle { .. } => {
+                                unreachable!("rejected by the parser")
+                            }
+                        }
+                    }
+                }
+            }
         }
     }
     let classes = desugar_classes_or_ids("class", classes_static, classes_toggled);
     let ids = desugar_classes_or_ids("id", ids, vec![]);
-    classes.into_iter().chain(ids).chain(named_attrs).collect()
+    let named_attrs = classes
+        .into_iter()
+        .chain(ids)
+        .map(|attr| (attr, None))
+        .chain(named_attrs)
+        .collect();
+    (named_attrs, raw_attrs)
 }
 
 fn desugar_classes_or_ids(
     attr_name: &'static str,
     values_static: Vec<Markup>,
-    values_toggled: Vec<(Markup, Toggler)>,
+    values_toggled: Vec<(Markup, Toggler, Option<Markup>)>,
 ) -> Option<NamedAttr> {
     if values_static.is_empty() && values_toggled.is_empty() {
         return None;
@@ -209,19 +1166,31 @@ fn desugar_classes_or_ids(
     for name in values_static {
         markups.extend(prepend_leading_space(name, &mut leading_space));
     }
-    for (name, Toggler { cond, cond_span }) in values_toggled {
+    for (name, Toggler { cond, cond_span }, else_name) in values_toggled {
+        // Only one branch is rendered, so both need the same leading space
+        let mut else_leading_space = leading_space;
+        let else_body = else_name.map(|else_name| Block {
+            markups: prepend_leading_space(else_name, &mut else_leading_space),
+            outer_span: cond_span,
+        });
         let body = Block {
             markups: prepend_leading_space(name, &mut leading_space),
             // TODO: is this correct?
             outer_span: cond_span,
         };
-        markups.push(Markup::Special {
-            segments: vec![Special {
+        let mut segments = vec![Special {
+            at_span: SpanRange::call_site(),
+            head: quote!(if (#cond)),
+            body,
+        }];
+        if let Some(else_body) = else_body {
+            segments.push(Special {
                 at_span: SpanRange::call_site(),
-                head: quote!(if (#cond)),
-                body,
-            }],
-        });
+                head: quote!(else),
+                body: else_body,
+            });
+        }
+        markups.push(Markup::Special { segments });
     }
     Some(NamedAttr {
         name: TokenStream::from(TokenTree::Ident(Ident::new(attr_name, Span::call_site()))),
@@ -251,14 +1220,23 @@ fn prepend_leading_space(name: Markup, leading_space: &mut bool) -> Vec<Markup>
 
 struct Builder {
     output_ident: TokenTree,
+    options: Options,
+    /// Whether escaped text is going into an attribute value.
+    in_attribute: bool,
+    /// Items that don't depend on where they are, which are put before the
+    /// other statements so that they don't split up static strings.
+    items: Vec<TokenTree>,
     tokens: Vec<TokenTree>,
     tail: String,
 }
 
 impl Builder {
-    fn new(output_ident: TokenTree) -> Builder {
+    fn new(output_ident: TokenTree, options: Options) -> Builder {
         Builder {
             output_ident,
+            options,
+            in_attribute: false,
+            items: Vec::new(),
             tokens: Vec::new(),
             tail: String::new(),
         }
@@ -269,7 +1247,11 @@ impl Builder {
     }
 
     fn push_escaped(&mut self, string: &str) {
-        escape::escape_to_string(string, &mut self.tail);
+        if self.in_attribute && self.options.quote_style == QuoteStyle::Single {
+            escape::escape_single_quoted_to_string(string, &mut self.tail);
+        } else {
+            escape::escape_to_string(string, &mut self.tail);
+        }
     }
 
     fn push_tokens(&mut self, tokens: TokenStream) {
@@ -277,6 +1259,10 @@ impl Builder {
         self.tokens.extend(tokens);
     }
 
+    fn push_item(&mut self, item: TokenStream) {
+        self.items.extend(item);
+    }
+
     fn cut(&mut self) {
         if self.tail.is_empty() {
             return;
@@ -292,6 +1278,111 @@ impl Builder {
 
     fn finish(mut self) -> TokenStream {
         self.cut();
-        self.tokens.into_iter().collect()
+        self.items.into_iter().chain(self.tokens).collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proc_macro2::{Ident, Span, TokenTree};
+    use quote::quote;
+
+    use super::{generate, Options};
+    use crate::parse::parse;
+
+    fn expand(input: proc_macro2::TokenStream) -> String {
+        let output_ident = TokenTree::Ident(Ident::new("output", Span::call_site()));
+        generate(parse(input), output_ident, Options::default()).to_string()
+    }
+
+    #[test]
+    fn literal_splices_are_static() {
+        let output = expand(quote!(p { ("<hi>") (('!')) }));
+        assert_eq!(
+            output,
+            quote!(output.push_str("<p>&lt;hi&gt;!</p>");).to_string()
+        );
+    }
+
+    #[test]
+    fn shared_fields_are_hoisted() {
+        let output = expand(quote!(@if wide { td { (user.name) } } @else { p { (user.name) } }));
+        assert!(output.contains("__maud_shared_0"));
+    }
+
+    #[test]
+    fn shared_calls_are_not_hoisted() {
+        let output = expand(quote!(@if wide { td { (total()) } } @else { p { (total()) } }));
+        assert!(!output.contains("__maud_shared"));
+        let output = expand(quote!(@if user.is_admin() { (user.name) } @else { (user.name) }));
+        assert!(!output.contains("__maud_shared"));
+    }
+
+    #[test]
+    fn repeated_statics_are_interned() {
+        let output = expand(quote!(p { (a) } p { (b) } p { (c) }));
+        assert_eq!(
+            output,
+            quote!(
+                const __MAUD_STATIC_0: &str = "</p><p>";
+                output.push_str("<p>");
+                maud::macro_private::render_to!(&(a), &mut output);
+                output.push_str(__MAUD_STATIC_0);
+                maud::macro_private::render_to!(&(b), &mut output);
+                output.push_str(__MAUD_STATIC_0);
+                maud::macro_private::render_to!(&(c), &mut output);
+                output.push_str("</p>");
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn many_repeated_statics_share_one_const() {
+        let cells = (0..50).map(|i| quote!(td { (#i) }));
+        let output = expand(quote!(#(#cells)*));
+        assert_eq!(output.matches("const").count(), 1);
+        assert_eq!(output.matches("\"</td><td>\"").count(), 1);
+        assert_eq!(output.matches("__MAUD_STATIC_0").count(), 50);
+    }
+
+    #[test]
+    fn env_values_are_static() {
+        let output = expand(quote!(p { @env("CARGO_PKG_NAME") "!" }));
+        assert_eq!(
+            output,
+            quote!(
+                const _: &str = ::core::env!("CARGO_PKG_NAME");
+                output.push_str("<p>maud_macros!</p>");
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn build_only_blocks_are_cfg_gated() {
+        let output = expand(quote!(p { @debug_only { "debug" } @release_only { (a) } }));
+        assert_eq!(
+            output,
+            quote!(
+                output.push_str("<p>");
+                #[cfg(debug_assertions)]
+                {
+                    output.push_str("debug");
+                }
+                #[cfg(not(debug_assertions))]
+                {
+                    maud::macro_private::render_to!(&(a), &mut output);
+                }
+                output.push_str("</p>");
+            )
+            .to_string()
+        );
+    }
+
+    #[test]
+    fn other_splices_are_dynamic() {
+        let output = expand(quote!(("a".to_owned())));
+        assert!(output.contains("render_to"));
     }
 }
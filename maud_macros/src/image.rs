@@ -0,0 +1,147 @@
+//! Reading the dimensions of an image from its header, for `@img`.
+
+/// Returns the width and height of a PNG, GIF, JPEG or WebP image, or `None`
+/// if the format isn't recognized or the header is cut short.
+///
+/// Only the header is read, so the rest of the file isn't checked.
+pub fn dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        png(bytes)
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some((u16_le(bytes, 6)?, u16_le(bytes, 8)?))
+    } else if bytes.starts_with(b"\xff\xd8") {
+        jpeg(bytes)
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        webp(bytes)
+    } else {
+        None
+    }
+}
+
+fn png(bytes: &[u8]) -> Option<(u32, u32)> {
+    // The first chunk is always `IHDR`, which starts with the dimensions
+    if bytes.get(12..16) != Some(b"IHDR") {
+        return None;
+    }
+    Some((u32_be(bytes, 16)?, u32_be(bytes, 20)?))
+}
+
+fn jpeg(bytes: &[u8]) -> Option<(u32, u32)> {
+    let mut i = 2;
+    loop {
+        // Markers can be padded with any number of `0xff` bytes
+        while *bytes.get(i)? == 0xff && *bytes.get(i + 1)? == 0xff {
+            i += 1;
+        }
+        if *bytes.get(i)? != 0xff {
+            return None;
+        }
+        let marker = *bytes.get(i + 1)?;
+        i += 2;
+        match marker {
+            // Markers without a segment
+            0x01 | 0xd0..=0xd7 => continue,
+            // Start of frame, except for DHT, JPG and DAC, which share the range
+            0xc0..=0xcf if !matches!(marker, 0xc4 | 0xc8 | 0xcc) => {
+                let height = u16_be(bytes, i + 3)?;
+                let width = u16_be(bytes, i + 5)?;
+                return Some((width, height));
+            }
+            // Start of scan, or end of image, without a frame
+            0xda | 0xd9 => return None,
+            _ => i += u16_be(bytes, i)? as usize,
+        }
+    }
+}
+
+fn webp(bytes: &[u8]) -> Option<(u32, u32)> {
+    match bytes.get(12..16)? {
+        // Lossy
+        b"VP8 " => {
+            if bytes.get(23..26) != Some(b"\x9d\x01\x2a") {
+                return None;
+            }
+            Some((u16_le(bytes, 26)? & 0x3fff, u16_le(bytes, 28)? & 0x3fff))
+        }
+        // Lossless, with both dimensions minus one packed into 28 bits
+        b"VP8L" => {
+            if *bytes.get(20)? != 0x2f {
+                return None;
+            }
+            let bits = u32::from_le_bytes(bytes.get(21..25)?.try_into().ok()?);
+            Some(((bits & 0x3fff) + 1, ((bits >> 14) & 0x3fff) + 1))
+        }
+        // Extended, with the canvas size minus one in 24 bits each
+        b"VP8X" => Some((u24_le(bytes, 24)? + 1, u24_le(bytes, 27)? + 1)),
+        _ => None,
+    }
+}
+
+fn u16_le(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_le_bytes(bytes.get(at..at + 2)?.try_into().ok()?).into())
+}
+
+fn u16_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u16::from_be_bytes(bytes.get(at..at + 2)?.try_into().ok()?).into())
+}
+
+fn u24_le(bytes: &[u8], at: usize) -> Option<u32> {
+    let b = bytes.get(at..at + 3)?;
+    Some(u32::from(b[0]) | u32::from(b[1]) << 8 | u32::from(b[2]) << 16)
+}
+
+fn u32_be(bytes: &[u8], at: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(bytes.get(at..at + 4)?.try_into().ok()?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::dimensions;
+
+    #[test]
+    fn png() {
+        let mut bytes = b"\x89PNG\r\n\x1a\n\0\0\0\x0dIHDR".to_vec();
+        bytes.extend(640u32.to_be_bytes());
+        bytes.extend(480u32.to_be_bytes());
+        assert_eq!(dimensions(&bytes), Some((640, 480)));
+        assert_eq!(dimensions(&bytes[..20]), None);
+    }
+
+    #[test]
+    fn gif() {
+        assert_eq!(dimensions(b"GIF89a\x20\x03\x58\x02"), Some((800, 600)));
+    }
+
+    #[test]
+    fn jpeg() {
+        let bytes = [
+            0xff, 0xd8, // Start of image
+            0xff, 0xe0, 0x00, 0x04, 0x00, 0x00, // APP0, with two bytes of data
+            0xff, 0xff, 0xc2, 0x00, 0x11, 0x08, // Padded SOF2, with 8-bit samples
+            0x01, 0x2c, 0x01, 0x90, // 300 high, 400 wide
+        ];
+        assert_eq!(dimensions(&bytes), Some((400, 300)));
+        assert_eq!(dimensions(&[0xff, 0xd8, 0xff, 0xd9]), None);
+    }
+
+    #[test]
+    fn webp() {
+        let mut lossy = b"RIFF\0\0\0\0WEBPVP8 \0\0\0\0\0\0\0\x9d\x01\x2a".to_vec();
+        lossy.extend([0x40, 0x01, 0xf0, 0x00]);
+        assert_eq!(dimensions(&lossy), Some((320, 240)));
+
+        let mut lossless = b"RIFF\0\0\0\0WEBPVP8L\0\0\0\0\x2f".to_vec();
+        let bits: u32 = 99 | 49 << 14;
+        lossless.extend(bits.to_le_bytes());
+        assert_eq!(dimensions(&lossless), Some((100, 50)));
+
+        let mut extended = b"RIFF\0\0\0\0WEBPVP8X\0\0\0\0\0\0\0\0".to_vec();
+        extended.extend([0xff, 0x03, 0x00, 0x2b, 0x02, 0x00]);
+        assert_eq!(dimensions(&extended), Some((1024, 556)));
+    }
+
+    #[test]
+    fn unknown() {
+        assert_eq!(dimensions(b"<svg></svg>"), None);
+    }
+}
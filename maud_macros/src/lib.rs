@@ -7,31 +7,292 @@ extern crate proc_macro;
 
 mod ast;
 mod escape;
+mod form;
 mod generate;
+mod image;
+#[cfg(feature = "lints")]
+mod lint;
 mod parse;
 
-use proc_macro2::{Ident, Span, TokenStream, TokenTree};
-use proc_macro_error::proc_macro_error;
+use proc_macro2::{Ident, Spacing, Span, TokenStream, TokenTree};
+use proc_macro_error::{abort_call_site, abort_if_dirty, proc_macro_error};
 use quote::quote;
 
+use crate::generate::{Options, QuoteStyle};
+
 #[proc_macro]
 #[proc_macro_error]
 pub fn html(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
-    expand(input.into()).into()
+    expand(input.into(), Options::default()).into()
 }
 
-fn expand(input: TokenStream) -> TokenStream {
-    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+/// Like `html!`, but delimits attribute values with single quotes
+/// (`attr='value'`) instead of double quotes.
+#[proc_macro]
+#[proc_macro_error]
+pub fn html_single_quoted(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let options = Options {
+        quote_style: QuoteStyle::Single,
+        ..Options::default()
+    };
+    expand(input.into(), options).into()
+}
+
+/// Like `html!`, but leaves out end tags where the HTML spec allows it.
+///
+/// This applies to `li`, `option`, `td`, `tr` and `p` elements, when the
+/// element that follows them (or the end of their parent element) is known
+/// at compile time. For example, `ul { li { "a" } li { "b" } }` renders as
+/// `<ul><li>a<li>b</ul>`.
+///
+/// See the [book](https://maud.lambda.xyz/elements-attributes.html) for the
+/// exact rules.
+#[proc_macro]
+#[proc_macro_error]
+pub fn html_minify(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let options = Options {
+        omit_end_tags: true,
+        ..Options::default()
+    };
+    expand(input.into(), options).into()
+}
+
+/// Like `html!`, but adds `loading="lazy"` and `decoding="async"` to every
+/// `img` and `iframe` element.
+///
+/// Attributes written in the template take precedence, so `img src="x"
+/// loading="eager";` keeps `loading="eager"` and only gains
+/// `decoding="async"`. Elements with a raw attribute splice, as in
+/// `img (attrs);`, are left as they are.
+#[proc_macro]
+#[proc_macro_error]
+pub fn html_lazy(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let options = Options {
+        lazy_media: true,
+        ..Options::default()
+    };
+    expand(input.into(), options).into()
+}
+
+/// Like `html!`, but for templates with no dynamic content. The result is a
+/// `PreEscaped<&'static str>`, which can be used in a `const`.
+///
+/// Splices, control structures, and toggles are compile errors.
+#[proc_macro]
+#[proc_macro_error]
+pub fn html_static(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_static(input.into()).into()
+}
+
+/// Derives a `render_fields()` method, which renders a labeled input for
+/// each field of a struct.
+///
+/// Fields of type `String` and `&str` become text inputs, integers and
+/// floats become number inputs, and `bool`s become checkboxes. An `Option`
+/// of one of these renders as that type, leaving the value out if it's
+/// `None`.
+#[proc_macro_derive(MaudForm)]
+#[proc_macro_error]
+pub fn maud_form(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    form::derive(syn::parse_macro_input!(input)).into()
+}
+
+/// Like `html!`, but builds the output in a `bumpalo` arena.
+///
+/// The first argument is the `&Bump` to allocate in, followed by a comma
+/// and the template. The result is a
+/// `PreEscaped<bumpalo::collections::String>`.
+///
+/// Requires the `bumpalo` feature on the `maud` crate.
+#[proc_macro]
+#[proc_macro_error]
+pub fn html_in(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_in(input.into()).into()
+}
+
+/// Like `html!`, but with a context value whose fields can be tested with
+/// `@flag(field) { ... }`.
+///
+/// The first argument is the context, followed by a comma and the template.
+/// The context is evaluated once and borrowed; a field tested by `@flag`
+/// can be a `bool` or an `Option`.
+#[proc_macro]
+#[proc_macro_error]
+pub fn html_with(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_with(input.into()).into()
+}
+
+/// Like `html!`, but writes to a `core::fmt::Write` instead of returning
+/// `Markup`.
+///
+/// The first argument is a `&mut` reference to the writer, followed by a
+/// comma and the template. The result is a `core::fmt::Result`; after the
+/// first error from the writer, the rest of the template is skipped.
+#[proc_macro]
+#[proc_macro_error]
+pub fn html_fmt(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    expand_fmt(input.into()).into()
+}
+
+fn expand(input: TokenStream, options: Options) -> TokenStream {
+    let (buffer_type, input) = split_buffer_arg(input);
     // Heuristic: the size of the resulting markup tends to correlate with the
     // code size of the template itself
     let size_hint = input.to_string().len();
     let markups = parse::parse(input);
-    let stmts = generate::generate(markups, output_ident.clone());
+    match buffer_type {
+        Some(buffer_type) => expand_buffer(buffer_type, markups, size_hint, options),
+        None => expand_markups(markups, size_hint, options),
+    }
+}
+
+/// Builds the output in a `maud::Buffer` of the given type, for
+/// `html!(buffer = Type, ...)`.
+fn expand_buffer(
+    buffer_type: TokenStream,
+    markups: Vec<ast::Markup>,
+    size_hint: usize,
+    options: Options,
+) -> TokenStream {
+    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+    let options = Options {
+        custom_buffer: true,
+        ..options
+    };
+    let stmts = generate::generate(markups, output_ident.clone(), options);
+    quote!({
+        extern crate maud;
+        let mut #output_ident = maud::macro_private::BufferWriter::<#buffer_type>::with_capacity(#size_hint);
+        #stmts
+        maud::PreEscaped(#output_ident.finish())
+    })
+}
+
+fn expand_with(input: TokenStream) -> TokenStream {
+    let (context, input) = split_first_arg(input, "a context");
+    let context_ident = Ident::new("__maud_context", Span::mixed_site());
+    let size_hint = input.to_string().len();
+    let markups = parse::parse_with_context(input, context_ident.clone());
+    let output = expand_markups(markups, size_hint, Options::default());
+    quote!({
+        let #context_ident = &(#context);
+        #output
+    })
+}
+
+fn expand_markups(markups: Vec<ast::Markup>, size_hint: usize, options: Options) -> TokenStream {
+    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+    let stmts = generate::generate(markups, output_ident.clone(), options);
+    #[cfg(not(feature = "alloc-counting"))]
+    let (buffer, output) = (
+        quote!(alloc::string::String::with_capacity(#size_hint)),
+        quote!(#output_ident),
+    );
+    #[cfg(feature = "alloc-counting")]
+    let (buffer, output) = (
+        quote!(maud::macro_private::CountingString::with_capacity(#size_hint)),
+        quote!(#output_ident.finish(concat!(file!(), ":", line!()))),
+    );
     quote!({
         extern crate alloc;
         extern crate maud;
-        let mut #output_ident = alloc::string::String::with_capacity(#size_hint);
+        let mut #output_ident = #buffer;
+        #stmts
+        maud::PreEscaped(#output)
+    })
+}
+
+fn expand_static(input: TokenStream) -> TokenStream {
+    let markups = parse::parse_static(input);
+    abort_if_dirty();
+    let (output, dependencies) = generate::generate_static(markups, Options::default());
+    quote!({
+        extern crate maud;
+        #dependencies
+        maud::PreEscaped(#output)
+    })
+}
+
+fn expand_in(input: TokenStream) -> TokenStream {
+    let (arena, input) = split_first_arg(input, "an arena");
+    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+    let size_hint = input.to_string().len();
+    let markups = parse::parse(input);
+    let options = Options {
+        custom_buffer: true,
+        ..Options::default()
+    };
+    let stmts = generate::generate(markups, output_ident.clone(), options);
+    quote!({
+        extern crate maud;
+        let mut #output_ident =
+            maud::macro_private::bumpalo::collections::String::with_capacity_in(#size_hint, #arena);
         #stmts
         maud::PreEscaped(#output_ident)
     })
 }
+
+fn expand_fmt(input: TokenStream) -> TokenStream {
+    let (writer, input) = split_first_arg(input, "a writer");
+    let output_ident = TokenTree::Ident(Ident::new("__maud_output", Span::mixed_site()));
+    let markups = parse::parse(input);
+    let options = Options {
+        custom_buffer: true,
+        ..Options::default()
+    };
+    let stmts = generate::generate(markups, output_ident.clone(), options);
+    quote!({
+        extern crate maud;
+        let mut #output_ident = maud::macro_private::FmtWriter::new(#writer);
+        #stmts
+        #output_ident.finish()
+    })
+}
+
+/// Splits off a leading `buffer = Type,`, if there is one.
+fn split_buffer_arg(input: TokenStream) -> (Option<TokenStream>, TokenStream) {
+    let mut tokens = input.clone().into_iter();
+    match (tokens.next(), tokens.next()) {
+        (Some(TokenTree::Ident(ident)), Some(TokenTree::Punct(punct)))
+            if ident == "buffer" && punct.as_char() == '=' && punct.spacing() == Spacing::Alone => {
+        }
+        _ => return (None, input),
+    }
+    // Commas inside angle brackets, as in `Type<A, B>`, are part of the type
+    let mut depth = 0usize;
+    let mut buffer_type = TokenStream::new();
+    loop {
+        match tokens.next() {
+            Some(TokenTree::Punct(ref punct)) if punct.as_char() == ',' && depth == 0 => break,
+            Some(token) => {
+                if let TokenTree::Punct(ref punct) = token {
+                    match punct.as_char() {
+                        '<' => depth += 1,
+                        '>' => depth = depth.saturating_sub(1),
+                        _ => {}
+                    }
+                }
+                buffer_type.extend([token]);
+            }
+            None => {
+                abort_call_site!("expected a buffer type, followed by a comma and the template")
+            }
+        }
+    }
+    (Some(buffer_type), tokens.collect())
+}
+
+/// Splits off the tokens before the first comma, which name what the
+/// template is rendered into.
+fn split_first_arg(input: TokenStream, what: &str) -> (TokenStream, TokenStream) {
+    let mut input = input.into_iter();
+    let mut arg = TokenStream::new();
+    loop {
+        match input.next() {
+            Some(TokenTree::Punct(ref punct)) if punct.as_char() == ',' => break,
+            Some(token) => arg.extend([token]),
+            None => abort_call_site!("expected {}, followed by a comma and the template", what),
+        }
+    }
+    (arg, input.collect())
+}
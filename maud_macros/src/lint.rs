@@ -0,0 +1,177 @@
+//! Opt-in lints, enabled with the `lints` feature.
+//!
+//! Procedural macros can't emit warnings on stable Rust, so each warning is
+//! reported by referring to a `#[deprecated]` item, spanned to the code
+//! that caused it.
+
+use proc_macro2::{Delimiter, Ident, Spacing, TokenStream, TokenTree};
+use quote::quote_spanned;
+
+use crate::ast::{Block, ElementBody, Markup};
+
+/// Returns code that emits a warning for each problem found in `markups`.
+pub fn lints(markups: &[Markup]) -> TokenStream {
+    let mut warnings = TokenStream::new();
+    shadowing(markups, &mut Vec::new(), &mut warnings);
+    warnings
+}
+
+/// Warns when a `@for`, `@let` or `@define` binding shadows an earlier one
+/// in the same template.
+///
+/// `scope` holds the bindings that are visible so far. This follows Rust's
+/// scoping rules for the generated code: a `@let` in an element body is
+/// visible to the element's later siblings, but one in a block or control
+/// structure is not.
+fn shadowing(markups: &[Markup], scope: &mut Vec<Ident>, warnings: &mut TokenStream) {
+    for markup in markups {
+        match markup {
+            Markup::Let {
+                tokens, else_block, ..
+            } => {
+                if let Some(else_block) = else_block {
+                    shadowing_in_block(else_block, &[], scope, warnings);
+                }
+                let pattern = tokens
+                    .clone()
+                    .into_iter()
+                    .skip(1)
+                    .take_while(|token| !is_let_pattern_end(token));
+                bind(pattern_bindings(pattern), scope, warnings);
+            }
+            Markup::Block(block) => shadowing_in_block(block, &[], scope, warnings),
+            Markup::Element {
+                body: ElementBody::Block { block },
+                ..
+            } => shadowing(&block.markups, scope, warnings),
+            Markup::Special { segments } => {
+                for segment in segments {
+                    let mut head = segment.head.clone().into_iter();
+                    let bindings = match head.next() {
+                        Some(TokenTree::Ident(keyword)) if keyword == "for" => {
+                            pattern_bindings(head.take_while(
+                                |token| !matches!(token, TokenTree::Ident(ident) if ident == "in"),
+                            ))
+                        }
+                        _ => Vec::new(),
+                    };
+                    shadowing_in_block(&segment.body, &bindings, scope, warnings);
+                }
+            }
+            Markup::Slot { body, .. } | Markup::RawBlock { body, .. } => {
+                shadowing_in_block(body, &[], scope, warnings)
+            }
+            Markup::Define {
+                name, params, body, ..
+            } => {
+                let bindings = params
+                    .iter()
+                    .flat_map(|param| {
+                        pattern_bindings(
+                            param
+                                .clone()
+                                .into_iter()
+                                .take_while(|token| !is_let_pattern_end(token)),
+                        )
+                    })
+                    .collect::<Vec<_>>();
+                shadowing_in_block(body, &bindings, scope, warnings);
+                bind(vec![name.clone()], scope, warnings);
+            }
+            Markup::Match { arms, .. } => {
+                for arm in arms {
+                    shadowing_in_block(&arm.body, &[], scope, warnings);
+                }
+            }
+            Markup::ParseError { .. }
+            | Markup::Literal { .. }
+            | Markup::Symbol { .. }
+            | Markup::Splice { .. }
+            | Markup::Use { .. }
+            | Markup::Dependency { .. }
+            | Markup::Element { .. } => {}
+        }
+    }
+}
+
+/// Checks a block that has its own scope, with `bindings` declared at the
+/// start of it.
+fn shadowing_in_block(
+    block: &Block,
+    bindings: &[Ident],
+    scope: &mut Vec<Ident>,
+    warnings: &mut TokenStream,
+) {
+    let len = scope.len();
+    bind(bindings.to_vec(), scope, warnings);
+    shadowing(&block.markups, scope, warnings);
+    scope.truncate(len);
+}
+
+fn bind(bindings: Vec<Ident>, scope: &mut Vec<Ident>, warnings: &mut TokenStream) {
+    for binding in bindings {
+        if scope.contains(&binding) {
+            let note = format!(
+                "`{}` shadows an earlier binding of the same name in this template",
+                binding
+            );
+            warnings.extend(quote_spanned! {binding.span()=>
+                {
+                    #[deprecated(note = #note)]
+                    #[allow(non_upper_case_globals)]
+                    const shadowed_binding: () = ();
+                    #[allow(clippy::let_unit_value)]
+                    let _ = shadowed_binding;
+                }
+            });
+        }
+        scope.push(binding);
+    }
+}
+
+/// Whether `token` ends the pattern of a `let` statement, either at the `=`
+/// or at a type annotation.
+fn is_let_pattern_end(token: &TokenTree) -> bool {
+    matches!(token, TokenTree::Punct(punct) if punct.as_char() == '=' || (punct.as_char() == ':' && punct.spacing() == Spacing::Alone))
+}
+
+/// Returns the names bound by a pattern.
+///
+/// This is a heuristic, as we can't resolve names: a lowercase identifier is
+/// taken to be a binding, unless it's a path segment or a struct field name.
+fn pattern_bindings(pattern: impl IntoIterator<Item = TokenTree>) -> Vec<Ident> {
+    let tokens = pattern.into_iter().collect::<Vec<_>>();
+    let mut bindings = Vec::new();
+    for (i, token) in tokens.iter().enumerate() {
+        match token {
+            TokenTree::Ident(ident) => {
+                let name = ident.to_string();
+                let is_path_or_field = matches!(
+                    tokens.get(i + 1),
+                    Some(TokenTree::Punct(punct)) if punct.as_char() == ':'
+                ) || matches!(
+                    tokens.get(i + 1),
+                    Some(TokenTree::Group(group)) if group.delimiter() != Delimiter::None
+                );
+                let is_after_path = i > 0
+                    && matches!(
+                        &tokens[i - 1],
+                        TokenTree::Punct(punct) if punct.as_char() == ':'
+                    );
+                if !name.starts_with(|c: char| c.is_lowercase() || c == '_')
+                    || matches!(name.as_str(), "_" | "mut" | "ref" | "box")
+                    // Bound by the generated code, as for `@first` and `@last`
+                    || name.starts_with("__maud_")
+                    || is_path_or_field
+                    || is_after_path
+                {
+                    continue;
+                }
+                bindings.push(ident.clone());
+            }
+            TokenTree::Group(group) => bindings.extend(pattern_bindings(group.stream())),
+            TokenTree::Punct(_) | TokenTree::Literal(_) => {}
+        }
+    }
+    bindings
+}
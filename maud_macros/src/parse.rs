@@ -1,19 +1,43 @@
-use proc_macro2::{Delimiter, Ident, Literal, Spacing, Span, TokenStream, TokenTree};
+use std::slice;
+
+use proc_macro2::{Delimiter, Group, Ident, Literal, Spacing, Span, TokenStream, TokenTree};
 use proc_macro_error::{abort, abort_call_site, emit_error, SpanRange};
-use std::collections::HashMap;
 
+use quote::{quote, quote_spanned};
 use syn::Lit;
 
 use crate::ast;
 
 pub fn parse(input: TokenStream) -> Vec<ast::Markup> {
-    Parser::new(input).markups()
+    let markups = Parser::new(input).markups();
+    check_duplicate_ids(&markups, &mut Vec::new(), None);
+    markups
+}
+
+/// Parses the template of an `html_with!`, where `@flag` reads the fields
+/// of the variable `context`.
+pub fn parse_with_context(input: TokenStream, context: Ident) -> Vec<ast::Markup> {
+    let mut parser = Parser::new(input);
+    parser.context = Some(context);
+    let markups = parser.markups();
+    check_duplicate_ids(&markups, &mut Vec::new(), None);
+    markups
+}
+
+/// Parses the template of an `html_static!`, reporting anything that would
+/// be evaluated at runtime as an error.
+pub fn parse_static(input: TokenStream) -> Vec<ast::Markup> {
+    let markups = parse(input);
+    check_static(&markups);
+    markups
 }
 
 #[derive(Clone)]
 struct Parser {
     /// If we're inside an attribute, then this contains the attribute name.
     current_attr: Option<String>,
+    /// The variable holding the context of an `html_with!`, if this is one.
+    context: Option<Ident>,
     input: <TokenStream as IntoIterator>::IntoIter,
 }
 
@@ -29,6 +53,7 @@ impl Parser {
     fn new(input: TokenStream) -> Parser {
         Parser {
             current_attr: None,
+            context: None,
             input: input.into_iter(),
         }
     }
@@ -36,6 +61,7 @@ impl Parser {
     fn with_input(&self, input: TokenStream) -> Parser {
         Parser {
             current_attr: self.current_attr.clone(),
+            context: self.context.clone(),
             input: input.into_iter(),
         }
     }
@@ -76,6 +102,12 @@ impl Parser {
                     let keyword = TokenTree::Ident(ident.clone());
                     result.push(self.let_expr(punct.span(), keyword));
                 }
+                Some((TokenTree::Punct(ref punct), Some(TokenTree::Ident(ref ident))))
+                    if punct.as_char() == '@' && *ident == "define" =>
+                {
+                    self.advance2();
+                    result.push(self.define(punct.span(), ident.clone()));
+                }
                 _ => result.push(self.markup()),
             }
         }
@@ -109,15 +141,42 @@ impl Parser {
                                 self.if_expr(at_span, vec![keyword], &mut segments);
                                 ast::Markup::Special { segments }
                             }
+                            "unless" => {
+                                let mut segments = Vec::new();
+                                self.unless_expr(at_span, ident, &mut segments);
+                                ast::Markup::Special { segments }
+                            }
+                            "flag" => {
+                                let mut segments = Vec::new();
+                                self.flag_expr(at_span, ident, &mut segments);
+                                ast::Markup::Special { segments }
+                            }
                             "while" => self.while_expr(at_span, keyword),
                             "for" => self.for_expr(at_span, keyword),
                             "match" => self.match_expr(at_span, keyword),
-                            "let" => {
+                            "itemscope" | "itemprop" => self.microdata(at_span, ident),
+                            "slot" => self.slot(at_span, ident),
+                            "block" => self.block_expr(at_span, ident),
+                            "outlet" => self.outlet(at_span, ident),
+                            "use" => self.use_expr(at_span, ident),
+                            "env" => self.env_expr(at_span, ident),
+                            "stylesheet_if_exists" => self.stylesheet_if_exists(at_span, ident),
+                            "img" => self.img_expr(at_span, ident),
+                            "raw_block" => self.raw_block(at_span, ident),
+                            "debug_only" | "release_only" => self.build_only(at_span, ident),
+                            "first" | "last" => {
+                                let span = SpanRange {
+                                    first: at_span,
+                                    last: ident.span(),
+                                };
+                                abort!(span, "`@{}` only works inside a `@for` loop", ident);
+                            }
+                            "let" | "define" => {
                                 let span = SpanRange {
                                     first: at_span,
                                     last: ident.span(),
                                 };
-                                abort!(span, "`@let` only works inside a block");
+                                abort!(span, "`@{}` only works inside a block", ident);
                             }
                             other => {
                                 let span = SpanRange {
@@ -137,11 +196,11 @@ impl Parser {
             TokenTree::Ident(ident) => {
                 let ident_string = ident.to_string();
                 match ident_string.as_str() {
-                    "if" | "while" | "for" | "match" | "let" => {
+                    "if" | "else" | "while" | "for" | "match" | "let" => {
                         abort!(
                             ident,
                             "found keyword `{}`", ident_string;
-                            help = "should this be a `@{}`?", ident_string
+                            help = "did you mean `@{}`?", ident_string
                         );
                     }
                     "true" | "false" => {
@@ -258,6 +317,102 @@ impl Parser {
         self.else_if_expr(segments)
     }
 
+    /// Parses an `@unless` expression, which is sugar for `@if !(cond)`.
+    ///
+    /// The leading `@unless` should already be consumed.
+    fn unless_expr(&mut self, at_span: Span, keyword: Ident, segments: &mut Vec<ast::Special>) {
+        let mut cond = Vec::new();
+        let body = loop {
+            match self.next() {
+                Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
+                    break self.block(block.stream(), SpanRange::single_span(block.span()));
+                }
+                Some(TokenTree::Ident(ref let_keyword))
+                    if cond.is_empty() && *let_keyword == "let" =>
+                {
+                    abort!(
+                        let_keyword,
+                        "`@unless let` is not supported";
+                        help = "use `@if let` with an `@else` instead"
+                    );
+                }
+                Some(token) => cond.push(token),
+                None => {
+                    let span = SpanRange {
+                        first: at_span,
+                        last: keyword.span(),
+                    };
+                    abort!(span, "expected body for this `@unless`");
+                }
+            }
+        };
+        if cond.is_empty() {
+            let span = SpanRange {
+                first: at_span,
+                last: keyword.span(),
+            };
+            abort!(span, "expected condition for this `@unless`");
+        }
+        let cond = Group::new(Delimiter::Parenthesis, cond.into_iter().collect());
+        let if_keyword = Ident::new("if", keyword.span());
+        segments.push(ast::Special {
+            at_span: SpanRange::single_span(at_span),
+            head: quote_spanned!(keyword.span()=> #if_keyword !#cond),
+            body,
+        });
+        self.else_if_expr(segments)
+    }
+
+    /// Parses and renders a `@flag(field)` or `@flag(!field)` expression,
+    /// which tests a field of the `html_with!` context.
+    ///
+    /// The leading `@flag` should already be consumed.
+    fn flag_expr(&mut self, at_span: Span, keyword: Ident, segments: &mut Vec<ast::Special>) {
+        let span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let Some(context) = self.context.clone() else {
+            abort!(
+                span,
+                "`@flag` only works in `html_with!`";
+                help = "pass the context first, as in `html_with!(&flags, ...)`"
+            );
+        };
+        let args = match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                group.stream().into_iter().collect::<Vec<_>>()
+            }
+            _ => abort!(span, "expected `(field)` after `@flag`"),
+        };
+        let (negate, field) = match args.as_slice() {
+            [TokenTree::Ident(field)] => (None, field.clone()),
+            [TokenTree::Punct(punct), TokenTree::Ident(field)] if punct.as_char() == '!' => {
+                (Some(punct.clone()), field.clone())
+            }
+            _ => abort!(
+                span,
+                "expected `(field)` after `@flag`";
+                help = "name a field of the context, optionally after a `!`, as in `@flag(!is_mobile)`"
+            ),
+        };
+        let body = match self.next() {
+            Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
+                self.block(block.stream(), SpanRange::single_span(block.span()))
+            }
+            _ => abort!(span, "expected body for this `@flag`"),
+        };
+        let if_keyword = Ident::new("if", keyword.span());
+        segments.push(ast::Special {
+            at_span: SpanRange::single_span(at_span),
+            head: quote_spanned!(keyword.span()=>
+                #if_keyword #negate maud::macro_private::Toggle::is_on(&#context.#field)
+            ),
+            body,
+        });
+        self.else_if_expr(segments)
+    }
+
     /// Parses an optional `@else if` or `@else`.
     ///
     /// The leading `@else if` or `@else` should *not* already be consumed.
@@ -356,10 +511,16 @@ impl Parser {
                 }
             }
         }
+        let pattern_len = head.len() - 2;
         let body = loop {
             match self.next() {
                 Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
-                    break self.block(block.stream(), SpanRange::single_span(block.span()));
+                    let mut uses_position = false;
+                    let stream = loop_position_markers(block.stream(), &mut uses_position);
+                    if uses_position {
+                        head = position_head(head, pattern_len);
+                    }
+                    break self.block(stream, SpanRange::single_span(block.span()));
                 }
                 Some(token) => head.push(token),
                 None => {
@@ -506,13 +667,44 @@ impl Parser {
                 }
             }
         }
+        let mut else_block = None;
         loop {
             match self.next() {
                 Some(token) => match token {
                     TokenTree::Punct(ref punct) if punct.as_char() == ';' => {
-                        tokens.push(token.clone());
+                        if else_block.is_none() {
+                            tokens.push(token.clone());
+                        }
                         break;
                     }
+                    // As in Rust, an `else` after a `}` belongs to the
+                    // expression, rather than starting a let-else
+                    TokenTree::Ident(ref ident)
+                        if *ident == "else"
+                            && else_block.is_none()
+                            && !matches!(
+                                tokens.last(),
+                                Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Brace
+                            ) =>
+                    {
+                        match self.next() {
+                            Some(TokenTree::Group(ref group))
+                                if group.delimiter() == Delimiter::Brace =>
+                            {
+                                else_block =
+                                    Some(self.block(
+                                        group.stream(),
+                                        SpanRange::single_span(group.span()),
+                                    ));
+                            }
+                            _ => {
+                                abort!(ident, "expected body for this `else`");
+                            }
+                        }
+                    }
+                    _ if else_block.is_some() => {
+                        abort!(token, "expected `;` after `@let ... else` body");
+                    }
                     _ => tokens.push(token),
                 },
                 None => {
@@ -529,9 +721,453 @@ impl Parser {
         ast::Markup::Let {
             at_span: SpanRange::single_span(at_span),
             tokens: tokens.into_iter().collect(),
+            else_block,
+        }
+    }
+
+    /// Parses the `(slots, name)` arguments of `@slot` or `@outlet`.
+    fn slot_args(&mut self, at_span: Span, keyword: &Ident) -> (TokenStream, TokenStream) {
+        let span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let args = match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                group.stream()
+            }
+            _ => abort!(span, "expected `(slots, name)` after `@{}`", keyword),
+        };
+        let mut args = args.into_iter();
+        let slots = args
+            .by_ref()
+            .take_while(|token| !matches!(token, TokenTree::Punct(punct) if punct.as_char() == ','))
+            .collect::<TokenStream>();
+        let name = args.collect::<TokenStream>();
+        if slots.is_empty() || name.is_empty() {
+            abort!(span, "expected `(slots, name)` after `@{}`", keyword);
+        }
+        (slots, name)
+    }
+
+    /// Parses a `@slot(slots, name) { ... }` directive.
+    ///
+    /// The opening `@slot` should already be consumed.
+    fn slot(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let (slots, name) = self.slot_args(at_span, &keyword);
+        let body = match self.next() {
+            Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
+                self.block(block.stream(), SpanRange::single_span(block.span()))
+            }
+            _ => {
+                let span = SpanRange {
+                    first: at_span,
+                    last: keyword.span(),
+                };
+                abort!(span, "expected body for this `@slot`");
+            }
+        };
+        ast::Markup::Slot {
+            at_span: SpanRange::single_span(at_span),
+            slots,
+            name,
+            body,
+        }
+    }
+
+    /// Parses a `@block(block) { ... }` directive, which renders the
+    /// content of a `maud::Block` if it was overridden, or the body
+    /// otherwise.
+    ///
+    /// The opening `@block` should already be consumed.
+    fn block_expr(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let expr = match self.next() {
+            Some(TokenTree::Group(ref group))
+                if group.delimiter() == Delimiter::Parenthesis && !group.stream().is_empty() =>
+            {
+                group.stream()
+            }
+            _ => abort!(span, "expected `(block)` after `@block`"),
+        };
+        let default = match self.next() {
+            Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
+                self.block(block.stream(), SpanRange::single_span(block.span()))
+            }
+            _ => abort!(span, "expected default content for this `@block`"),
+        };
+        let content = Ident::new("__maud_block_content", Span::mixed_site());
+        let overridden = ast::Block {
+            markups: vec![ast::Markup::Splice {
+                expr: quote_spanned!(Span::mixed_site()=> #content),
+                outer_span: span,
+            }],
+            outer_span: span,
+        };
+        let mut segments = vec![ast::Special {
+            at_span: SpanRange::single_span(at_span),
+            head: quote_spanned!(Span::mixed_site()=>
+                if let Some(#content) = maud::macro_private::block_content(&(#expr))
+            ),
+            body: overridden,
+        }];
+        if !default.markups.is_empty() {
+            segments.push(ast::Special {
+                at_span: SpanRange::single_span(at_span),
+                head: TokenTree::Ident(Ident::new("else", keyword.span())).into(),
+                body: default,
+            });
+        }
+        ast::Markup::Special { segments }
+    }
+
+    /// Parses a `@raw_block { ... }`.
+    ///
+    /// The opening `@raw_block` should already be consumed.
+    fn raw_block(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let body = match self.next() {
+            Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
+                self.block(block.stream(), SpanRange::single_span(block.span()))
+            }
+            _ => {
+                let span = SpanRange {
+                    first: at_span,
+                    last: keyword.span(),
+                };
+                abort!(span, "expected body for this `@raw_block`");
+            }
+        };
+        ast::Markup::RawBlock {
+            at_span: SpanRange::single_span(at_span),
+            body,
         }
     }
 
+    /// Parses a `@debug_only { ... }` or `@release_only { ... }` block, whose
+    /// generated code is behind a `#[cfg]` on `debug_assertions`, so that
+    /// it's compiled out of the other kind of build.
+    ///
+    /// The opening `@debug_only` or `@release_only` should already be
+    /// consumed.
+    fn build_only(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let body = match self.next() {
+            Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
+                self.block(block.stream(), SpanRange::single_span(block.span()))
+            }
+            _ => {
+                let span = SpanRange {
+                    first: at_span,
+                    last: keyword.span(),
+                };
+                abort!(span, "expected body for this `@{}`", keyword);
+            }
+        };
+        let head = if keyword == "debug_only" {
+            quote_spanned!(keyword.span()=> #[cfg(debug_assertions)])
+        } else {
+            quote_spanned!(keyword.span()=> #[cfg(not(debug_assertions))])
+        };
+        ast::Markup::Special {
+            segments: vec![ast::Special {
+                at_span: SpanRange::single_span(at_span),
+                head,
+                body,
+            }],
+        }
+    }
+
+    /// Parses an `@outlet(slots, name)` directive, which is sugar for
+    /// splicing `slots.take(name)`.
+    ///
+    /// The opening `@outlet` should already be consumed.
+    fn outlet(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let (slots, name) = self.slot_args(at_span, &keyword);
+        let outer_span = SpanRange {
+            first: at_span,
+            last: ast::span_tokens(name.clone()).last,
+        };
+        ast::Markup::Splice {
+            expr: quote_spanned!(keyword.span()=> (#slots).take(#name)),
+            outer_span,
+        }
+    }
+
+    /// Parses a `@define name(args) { ... }` definition.
+    ///
+    /// The opening `@define` should already be consumed.
+    fn define(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let keyword_span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let name = match self.next() {
+            Some(TokenTree::Ident(name)) => name,
+            _ => abort!(keyword_span, "expected a name after `@define`"),
+        };
+        let params = match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                split_params(group.stream())
+            }
+            _ => abort!(name, "expected `(arguments)` after `@define {}`", name),
+        };
+        for param in &params {
+            if !has_type_annotation(param) {
+                abort!(
+                    ast::span_tokens(param.clone()),
+                    "`@define` arguments must have a type";
+                    help = "add a type, like `{}: &str`", param
+                );
+            }
+        }
+        let body = match self.next() {
+            Some(TokenTree::Group(ref block)) if block.delimiter() == Delimiter::Brace => {
+                self.block(block.stream(), SpanRange::single_span(block.span()))
+            }
+            _ => abort!(name, "expected body for this `@define`"),
+        };
+        ast::Markup::Define {
+            at_span: SpanRange::single_span(at_span),
+            name,
+            params,
+            body,
+        }
+    }
+
+    /// Parses a `@use name(args)` call of a `@define`.
+    ///
+    /// The opening `@use` should already be consumed.
+    fn use_expr(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let keyword_span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let name = match self.next() {
+            Some(TokenTree::Ident(name)) => name,
+            _ => abort!(keyword_span, "expected `name(arguments)` after `@use`"),
+        };
+        match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                ast::Markup::Use {
+                    at_span: SpanRange::single_span(at_span),
+                    name,
+                    args: group.stream(),
+                    args_span: SpanRange::single_span(group.span()),
+                }
+            }
+            _ => abort!(name, "expected `(arguments)` after `@use {}`", name),
+        }
+    }
+
+    /// Parses an `@env("NAME")` directive, which is replaced with the value of
+    /// that environment variable at compile time.
+    ///
+    /// The leading `@env` should already be consumed.
+    fn env_expr(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let keyword_span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let group = match self.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+            _ => abort!(keyword_span, "expected `(\"NAME\")` after `@env`"),
+        };
+        let span = SpanRange {
+            first: at_span,
+            last: group.span(),
+        };
+        let name = match syn::parse2::<syn::LitStr>(group.stream()) {
+            Ok(name) => name.value(),
+            Err(_) => abort!(
+                group,
+                "expected the name of an environment variable";
+                help = "write it as a string literal, like `@env(\"ASSET_URL\")`"
+            ),
+        };
+        match std::env::var(&name) {
+            Ok(content) => ast::Markup::Block(ast::Block {
+                markups: vec![
+                    // Tells Cargo to rebuild the template when the variable
+                    // changes
+                    ast::Markup::Dependency {
+                        item: quote!(
+                            const _: &str = ::core::env!(#name);
+                        ),
+                        span,
+                    },
+                    ast::Markup::Literal { content, span },
+                ],
+                outer_span: span,
+            }),
+            Err(std::env::VarError::NotPresent) => {
+                abort!(span, "environment variable `{}` is not set", name)
+            }
+            Err(std::env::VarError::NotUnicode(_)) => {
+                abort!(span, "environment variable `{}` is not valid Unicode", name)
+            }
+        }
+    }
+
+    /// Parses a `@stylesheet_if_exists("path")` or
+    /// `@stylesheet_if_exists("path", "href")` directive, which is replaced
+    /// with a `<link rel="stylesheet">` if the file exists at compile time,
+    /// and with nothing otherwise.
+    ///
+    /// The leading `@stylesheet_if_exists` should already be consumed.
+    fn stylesheet_if_exists(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let (path, href, span) = self.file_args(at_span, &keyword, "href", "static/theme.css");
+        let markups = if manifest_path(&path).is_file() {
+            let mut markups = self
+                .with_input(quote_spanned!(keyword.span()=> link rel="stylesheet" href=#href;))
+                .markups();
+            markups.push(file_dependency(&path, span));
+            markups
+        } else {
+            Vec::new()
+        };
+        ast::Markup::Block(ast::Block {
+            markups,
+            outer_span: span,
+        })
+    }
+
+    /// Parses an `@img("path") attrs;` or `@img("path", "src") attrs;`
+    /// directive, which becomes an `img` element with `width` and `height`
+    /// read from the image file at compile time.
+    ///
+    /// The leading `@img` should already be consumed.
+    fn img_expr(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let (path, src, span) = self.file_args(at_span, &keyword, "src", "static/logo.png");
+        let bytes = match std::fs::read(manifest_path(&path)) {
+            Ok(bytes) => bytes,
+            Err(error) => abort!(span, "can't read image `{}`: {}", path, error),
+        };
+        let (width, height) = match crate::image::dimensions(&bytes) {
+            Some(dimensions) => dimensions,
+            None => abort!(
+                span,
+                "can't read the dimensions of image `{}`", path;
+                help = "only PNG, GIF, JPEG and WebP images are supported"
+            ),
+        };
+        let width = Literal::string(&width.to_string());
+        let height = Literal::string(&height.to_string());
+        // The rest of the element, up to and including the `;`
+        let mut rest = Vec::new();
+        loop {
+            match self.next() {
+                Some(TokenTree::Punct(punct)) if punct.as_char() == ';' => {
+                    rest.push(TokenTree::Punct(punct));
+                    break;
+                }
+                Some(token) => rest.push(token),
+                None => abort!(
+                    span,
+                    "expected `;` after this `@img`";
+                    help = "other attributes can go before the `;`, as in `@img(\"logo.png\") alt=\"Logo\";`"
+                ),
+            }
+        }
+        let mut markups = self
+            .with_input(quote_spanned!(keyword.span()=>
+                img src=#src width=#width height=#height #(#rest)*
+            ))
+            .markups();
+        markups.push(file_dependency(&path, span));
+        ast::Markup::Block(ast::Block {
+            markups,
+            outer_span: span,
+        })
+    }
+
+    /// Parses the `("path")` or `("path", "url")` after a directive that
+    /// reads a file, returning the path, the URL (which is the path if
+    /// there's only one argument), and the span of the whole directive.
+    fn file_args(
+        &mut self,
+        at_span: Span,
+        keyword: &Ident,
+        url_name: &str,
+        example: &str,
+    ) -> (String, syn::LitStr, SpanRange) {
+        let keyword_span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let group = match self.next() {
+            Some(TokenTree::Group(group)) if group.delimiter() == Delimiter::Parenthesis => group,
+            _ => abort!(keyword_span, "expected `(\"path\")` after `@{}`", keyword),
+        };
+        let span = SpanRange {
+            first: at_span,
+            last: group.span(),
+        };
+        let parser = syn::punctuated::Punctuated::<syn::LitStr, syn::Token![,]>::parse_terminated;
+        let args = match syn::parse::Parser::parse2(parser, group.stream()) {
+            Ok(args) if (1..=2).contains(&args.len()) => args,
+            _ => abort!(
+                group,
+                "expected a path, and optionally a `{}`", url_name;
+                help = "write them as string literals, like `@{}(\"{}\", \"/{}\")`",
+                    keyword, example, example.rsplit('/').next().unwrap_or(example)
+            ),
+        };
+        let path = args[0].value();
+        let url = args.last().unwrap_or(&args[0]).clone();
+        (path, url, span)
+    }
+
+    /// Parses a `@itemscope(type)` or `@itemprop(name)` directive, which adds
+    /// microdata attributes to the element that follows it.
+    ///
+    /// The opening `@itemscope` or `@itemprop` should already be consumed.
+    fn microdata(&mut self, at_span: Span, keyword: Ident) -> ast::Markup {
+        let directive_span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let value = match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                ast::Markup::Splice {
+                    expr: group.stream(),
+                    outer_span: SpanRange::single_span(group.span()),
+                }
+            }
+            _ => abort!(directive_span, "expected `(...)` after `@{}`", keyword),
+        };
+        if self.peek().is_none() {
+            abort!(directive_span, "expected an element after `@{}`", keyword);
+        }
+        let (name, mut attrs, body) = match self.markup() {
+            ast::Markup::Element { name, attrs, body } => (name, attrs, body),
+            markup => {
+                abort!(
+                    markup.span(),
+                    "expected an element after `@{}`", keyword;
+                    note = directive_span.first => "`@{}` applies to the element that follows it", keyword;
+                );
+            }
+        };
+        let named_attr = |name: &str, attr_type| ast::Attr::Named {
+            named_attr: ast::NamedAttr {
+                name: TokenStream::from(TokenTree::Ident(Ident::new(name, keyword.span()))),
+                attr_type,
+            },
+        };
+        let injected = if keyword == "itemscope" {
+            vec![
+                named_attr("itemscope", ast::AttrType::Empty { toggler: None }),
+                named_attr("itemtype", ast::AttrType::Normal { value }),
+            ]
+        } else {
+            vec![named_attr("itemprop", ast::AttrType::Normal { value })]
+        };
+        attrs.splice(0..0, injected);
+        check_duplicate_attrs(&attrs);
+        ast::Markup::Element { name, attrs, body }
+    }
+
     /// Parses an element node.
     ///
     /// The element name should already be consumed.
@@ -559,6 +1195,46 @@ impl Parser {
                     semi_span: SpanRange::single_span(punct.span()),
                 }
             }
+            Some(TokenTree::Punct(ref punct)) if punct.as_char() == '=' => {
+                // Expression body
+                self.advance();
+                let mut expr = Vec::new();
+                loop {
+                    match self.next() {
+                        Some(TokenTree::Punct(ref punct)) if punct.as_char() == ';' => break,
+                        Some(token) => expr.push(token),
+                        None => {
+                            if expr.is_empty() {
+                                abort!(punct, "expected expression after `=`");
+                            }
+                            let mut span = ast::span_tokens(expr);
+                            span.first = punct.span();
+                            abort!(
+                                span,
+                                "expected `;` after element expression";
+                                help = "add a `;` at the end of the expression"
+                            );
+                        }
+                    }
+                }
+                if expr.is_empty() {
+                    abort!(punct, "expected expression after `=`");
+                }
+                let outer_span = ast::span_tokens(expr.clone());
+                let outer_span = SpanRange {
+                    first: punct.span(),
+                    last: outer_span.last,
+                };
+                ast::ElementBody::Block {
+                    block: ast::Block {
+                        markups: vec![ast::Markup::Splice {
+                            expr: expr.into_iter().collect(),
+                            outer_span,
+                        }],
+                        outer_span,
+                    },
+                }
+            }
             Some(_) => match self.markup() {
                 ast::Markup::Block(block) => ast::ElementBody::Block { block },
                 markup => {
@@ -570,7 +1246,15 @@ impl Parser {
                     );
                 }
             },
-            None => abort_call_site!("expected `;`, found end of macro"),
+            None => {
+                let span = ast::span_tokens(name.clone());
+                abort!(
+                    span,
+                    "expected body for element `{}`, found end of macro",
+                    ast::name_to_string(name);
+                    help = "add `{ ... }` for the contents of the element, or `;` if it's a void element"
+                );
+            }
         };
         ast::Markup::Element { name, attrs, body }
     }
@@ -589,10 +1273,16 @@ impl Parser {
                         assert!(self.current_attr.is_none());
                         self.current_attr = Some(ast::name_to_string(name.clone()));
                         let attr_type = match self.attr_toggler() {
-                            Some(toggler) => ast::AttrType::Optional { toggler },
+                            Some(toggler) => {
+                                let (toggler, fallback) = split_fallback(toggler);
+                                ast::AttrType::Optional { toggler, fallback }
+                            }
                             None => {
                                 let value = self.markup();
-                                ast::AttrType::Normal { value }
+                                match self.attr_toggler() {
+                                    Some(toggler) => ast::AttrType::Toggled { value, toggler },
+                                    None => ast::AttrType::Normal { value },
+                                }
                             }
                         };
                         self.current_attr = None;
@@ -629,10 +1319,20 @@ impl Parser {
                         self.advance();
                         let name = self.class_or_id_name();
                         let toggler = self.attr_toggler();
+                        let else_name = match self.peek() {
+                            Some(TokenTree::Punct(ref colon))
+                                if toggler.is_some() && colon.as_char() == ':' =>
+                            {
+                                self.advance();
+                                Some(self.class_or_id_name())
+                            }
+                            _ => None,
+                        };
                         attrs.push(ast::Attr::Class {
                             dot_span: SpanRange::single_span(punct.span()),
                             name,
                             toggler,
+                            else_name,
                         });
                     }
                     // ID shorthand
@@ -644,44 +1344,113 @@ impl Parser {
                             name,
                         });
                     }
+                    // Raw attributes
+                    Some(TokenTree::Group(ref group))
+                        if group.delimiter() == Delimiter::Parenthesis =>
+                    {
+                        self.advance();
+                        attrs.push(ast::Attr::Raw {
+                            expr: group.stream(),
+                            span: SpanRange::single_span(group.span()),
+                        });
+                    }
+                    // Attributes that share a condition
+                    Some(TokenTree::Punct(ref punct))
+                        if punct.as_char() == '@'
+                            && matches!(
+                                self.peek2(),
+                                Some((_, Some(TokenTree::Ident(ref keyword)))) if *keyword == "toggle"
+                            ) =>
+                    {
+                        self.advance();
+                        let keyword = match self.next() {
+                            Some(TokenTree::Ident(keyword)) => keyword,
+                            _ => unreachable!(),
+                        };
+                        attrs.push(self.toggle_attrs(punct.span(), keyword));
+                    }
                     // If it's not a valid attribute, backtrack and bail out
                     _ => break,
                 }
             }
         }
 
-        let mut attr_map: HashMap<String, Vec<SpanRange>> = HashMap::new();
-        let mut has_class = false;
-        for attr in &attrs {
-            let name = match attr {
-                ast::Attr::Class { .. } => {
-                    if has_class {
-                        // Only check the first class to avoid spurious duplicates
-                        continue;
+        check_duplicate_attrs(&attrs);
+
+        attrs
+    }
+
+    /// Parses a `@toggle(cond) { ... }` attribute group, with an optional
+    /// `@else { ... }` group after it.
+    ///
+    /// The opening `@toggle` should already be consumed.
+    fn toggle_attrs(&mut self, at_span: Span, keyword: Ident) -> ast::Attr {
+        let keyword_span = SpanRange {
+            first: at_span,
+            last: keyword.span(),
+        };
+        let cond = match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Parenthesis => {
+                group.stream()
+            }
+            _ => abort!(keyword_span, "expected `(condition)` after `@toggle`"),
+        };
+        let (then_attrs, mut span) = match self.next() {
+            Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Brace => (
+                self.toggle_group(group),
+                SpanRange::single_span(group.span()),
+            ),
+            _ => abort!(keyword_span, "expected attributes for this `@toggle`"),
+        };
+        let mut else_attrs = Vec::new();
+        if let Some((TokenTree::Punct(ref punct), Some(TokenTree::Ident(ref else_keyword)))) =
+            self.peek2()
+        {
+            if punct.as_char() == '@' && *else_keyword == "else" {
+                self.advance2();
+                match self.next() {
+                    Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Brace => {
+                        else_attrs = self.toggle_group(group);
+                        span = span.join_range(SpanRange::single_span(group.span()));
                     }
-                    has_class = true;
-                    "class".to_string()
-                }
-                ast::Attr::Id { .. } => "id".to_string(),
-                ast::Attr::Named { named_attr } => named_attr
-                    .name
-                    .clone()
-                    .into_iter()
-                    .map(|token| token.to_string())
-                    .collect(),
-            };
-            let entry = attr_map.entry(name).or_default();
-            entry.push(attr.span());
+                    _ => {
+                        let span = SpanRange {
+                            first: punct.span(),
+                            last: else_keyword.span(),
+                        };
+                        abort!(span, "expected attributes for this `@else`");
+                    }
+                }
+            }
+        }
+        ast::Attr::Toggle {
+            at_span: SpanRange::single_span(at_span),
+            cond,
+            then_attrs,
+            else_attrs,
+            span,
         }
+    }
 
-        for (name, spans) in attr_map {
-            if spans.len() > 1 {
-                let mut spans = spans.into_iter();
-                let first_span = spans.next().expect("spans should be non-empty");
-                abort!(first_span, "duplicate attribute `{}`", name);
+    /// Parses the attributes inside one branch of a `@toggle`.
+    fn toggle_group(&self, group: &Group) -> Vec<ast::Attr> {
+        let mut parser = self.with_input(group.stream());
+        let attrs = parser.attrs();
+        if let Some(token) = parser.next() {
+            abort!(token, "expected attribute");
+        }
+        for attr in &attrs {
+            match attr {
+                ast::Attr::Class {
+                    else_name: Some(_), ..
+                } => abort!(attr.span(), "`@toggle` can't contain a class with `:else`"),
+                ast::Attr::Raw { .. } => {
+                    abort!(attr.span(), "`@toggle` can't contain raw attributes")
+                }
+                ast::Attr::Toggle { .. } => abort!(attr.span(), "`@toggle` can't be nested"),
+                ast::Attr::Class { .. } | ast::Attr::Id { .. } | ast::Attr::Named { .. } => {}
             }
         }
-
         attrs
     }
 
@@ -695,6 +1464,9 @@ impl Parser {
     }
 
     /// Parses the `[cond]` syntax after an empty attribute or class shorthand.
+    ///
+    /// A class toggler may be followed by `:name`, giving a class to use
+    /// when the condition is false; that's handled by the caller.
     fn attr_toggler(&mut self) -> Option<ast::Toggler> {
         match self.peek() {
             Some(TokenTree::Group(ref group)) if group.delimiter() == Delimiter::Bracket => {
@@ -765,3 +1537,441 @@ impl Parser {
         }
     }
 }
+
+/// Replaces `@first` and `@last` in the body of a `@for` with the variables
+/// that hold them, setting `used` if there are any.
+///
+/// The bodies of nested `@for` loops are left alone, as their markers refer
+/// to the inner loop.
+fn loop_position_markers(stream: TokenStream, used: &mut bool) -> TokenStream {
+    let mut result = Vec::new();
+    let mut tokens = stream.into_iter().peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            TokenTree::Punct(ref punct) if punct.as_char() == '@' => match tokens.peek() {
+                Some(TokenTree::Ident(ident)) if *ident == "first" || *ident == "last" => {
+                    let name = format!("__maud_{}", ident);
+                    tokens.next();
+                    *used = true;
+                    result.push(TokenTree::Ident(Ident::new(&name, Span::mixed_site())));
+                }
+                Some(TokenTree::Ident(ident)) if *ident == "for" => {
+                    result.push(token);
+                    for token in tokens.by_ref() {
+                        let is_body = matches!(
+                            token,
+                            TokenTree::Group(ref group) if group.delimiter() == Delimiter::Brace
+                        );
+                        result.push(token);
+                        if is_body {
+                            break;
+                        }
+                    }
+                }
+                _ => result.push(token),
+            },
+            TokenTree::Group(ref group) => {
+                let mut new_group = Group::new(
+                    group.delimiter(),
+                    loop_position_markers(group.stream(), used),
+                );
+                new_group.set_span(group.span());
+                result.push(TokenTree::Group(new_group));
+            }
+            _ => result.push(token),
+        }
+    }
+    result.into_iter().collect()
+}
+
+/// Rewrites the head of a `@for` loop, `for pattern in expr`, so that it
+/// also binds the variables behind `@first` and `@last`. The pattern is the
+/// `pattern_len` tokens after `for`.
+fn position_head(head: Vec<TokenTree>, pattern_len: usize) -> Vec<TokenTree> {
+    let mut tokens = head.into_iter();
+    let for_keyword = tokens.next();
+    let pattern: TokenStream = tokens.by_ref().take(pattern_len).collect();
+    let in_keyword = tokens.next();
+    let expr: TokenStream = tokens.collect();
+    let first = Ident::new("__maud_first", Span::mixed_site());
+    let last = Ident::new("__maud_last", Span::mixed_site());
+    quote_spanned!(Span::mixed_site()=>
+        #for_keyword (#first, #last, #pattern) #in_keyword
+            maud::macro_private::loop_positions(#expr)
+    )
+    .into_iter()
+    .collect()
+}
+
+/// Resolves a path from a template against the directory of the crate's
+/// `Cargo.toml`.
+fn manifest_path(path: &str) -> std::path::PathBuf {
+    match std::env::var_os("CARGO_MANIFEST_DIR") {
+        Some(dir) => std::path::Path::new(&dir).join(path),
+        None => std::path::PathBuf::from(path),
+    }
+}
+
+/// Returns an item that includes the file at `path`, relative to the crate's
+/// `Cargo.toml`, so that Cargo rebuilds the template when the file changes.
+fn file_dependency(path: &str, span: SpanRange) -> ast::Markup {
+    let path = manifest_path(path);
+    let path = std::fs::canonicalize(&path).unwrap_or(path);
+    let path = Literal::string(&path.to_string_lossy());
+    ast::Markup::Dependency {
+        item: quote!(
+            const _: &[u8] = ::core::include_bytes!(#path);
+        ),
+        span,
+    }
+}
+
+/// Splits an optional attribute value, as in `[value ?? fallback]`, at the
+/// last top-level `??`.
+///
+/// Taking the last one means that a `?` operator can come just before it,
+/// as in `[parse(s)? ?? 0]`.
+fn split_fallback(toggler: ast::Toggler) -> (ast::Toggler, Option<TokenStream>) {
+    let tokens = toggler.cond.clone().into_iter().collect::<Vec<_>>();
+    let is_question =
+        |token: &TokenTree| matches!(token, TokenTree::Punct(punct) if punct.as_char() == '?');
+    let split = (1..tokens.len())
+        .rev()
+        .find(|&i| is_question(&tokens[i - 1]) && is_question(&tokens[i]));
+    let Some(split) = split else {
+        return (toggler, None);
+    };
+    let value = tokens[..split - 1].iter().cloned().collect::<TokenStream>();
+    let fallback = tokens[split + 1..].iter().cloned().collect::<TokenStream>();
+    if value.is_empty() || fallback.is_empty() {
+        abort!(
+            toggler.cond_span,
+            "expected `[value ?? fallback]`";
+            help = "the value must be an `Option`, and the fallback is rendered when it's `None`"
+        );
+    }
+    let toggler = ast::Toggler {
+        cond: value,
+        cond_span: toggler.cond_span,
+    };
+    (toggler, Some(fallback))
+}
+
+/// Splits the arguments of a `@define` on commas, leaving alone any commas
+/// within generic arguments like `HashMap<K, V>`.
+fn split_params(params: TokenStream) -> Vec<TokenStream> {
+    let mut result = Vec::new();
+    let mut current = Vec::new();
+    let mut angle_depth = 0usize;
+    let mut after_arrow_start = false;
+    for token in params {
+        if let TokenTree::Punct(ref punct) = token {
+            match punct.as_char() {
+                ',' if angle_depth == 0 => {
+                    result.push(current.drain(..).collect());
+                    after_arrow_start = false;
+                    continue;
+                }
+                '<' => angle_depth += 1,
+                // The `>` in `->` doesn't close anything
+                '>' if !after_arrow_start => angle_depth = angle_depth.saturating_sub(1),
+                _ => {}
+            }
+            after_arrow_start = punct.as_char() == '-' && punct.spacing() == Spacing::Joint;
+        } else {
+            after_arrow_start = false;
+        }
+        current.push(token);
+    }
+    if !current.is_empty() {
+        result.push(current.into_iter().collect());
+    }
+    result
+}
+
+/// Whether a `@define` argument has a type, as in `name: Type`.
+fn has_type_annotation(param: &TokenStream) -> bool {
+    let mut tokens = param.clone().into_iter();
+    while let Some(token) = tokens.next() {
+        if let TokenTree::Punct(ref punct) = token {
+            if punct.as_char() == ':' {
+                if punct.spacing() == Spacing::Alone {
+                    return true;
+                }
+                // Skip the second half of a `::`
+                tokens.next();
+            }
+        }
+    }
+    false
+}
+
+/// Aborts if an attribute is given more than once.
+fn check_duplicate_attrs(attrs: &[ast::Attr]) {
+    // Kept in source order, so that the error is the same on every build
+    let mut attr_list: Vec<(String, Vec<SpanRange>)> = Vec::new();
+    let mut has_class = false;
+    for attr in attrs {
+        let names = match attr {
+            ast::Attr::Class { .. } => {
+                if has_class {
+                    // Only check the first class to avoid spurious duplicates
+                    continue;
+                }
+                has_class = true;
+                vec![("class".to_string(), attr.span())]
+            }
+            ast::Attr::Toggle {
+                then_attrs,
+                else_attrs,
+                ..
+            } => {
+                // Only one branch is rendered, so a name may appear in both;
+                // each branch has already been checked on its own
+                let mut names: Vec<(String, SpanRange)> = Vec::new();
+                for inner in then_attrs.iter().chain(else_attrs) {
+                    let name = match inner {
+                        ast::Attr::Class { .. } if has_class => continue,
+                        ast::Attr::Class { .. } => "class".to_string(),
+                        ast::Attr::Id { .. } => "id".to_string(),
+                        ast::Attr::Named { named_attr } => attr_name_string(named_attr),
+                        _ => continue,
+                    };
+                    if !names.iter().any(|(other, _)| *other == name) {
+                        names.push((name, inner.span()));
+                    }
+                }
+                has_class |= names.iter().any(|(name, _)| name == "class");
+                names
+            }
+            ast::Attr::Id { .. } => vec![("id".to_string(), attr.span())],
+            // The contents aren't known until run time
+            ast::Attr::Raw { .. } => continue,
+            ast::Attr::Named { named_attr } => vec![(attr_name_string(named_attr), attr.span())],
+        };
+        for (name, span) in names {
+            match attr_list.iter_mut().find(|(other, _)| *other == name) {
+                Some((_, spans)) => spans.push(span),
+                None => attr_list.push((name, vec![span])),
+            }
+        }
+    }
+
+    for (name, spans) in attr_list {
+        if spans.len() > 1 {
+            let mut spans = spans.into_iter();
+            let first_span = spans.next().expect("spans should be non-empty");
+            abort!(first_span, "duplicate attribute `{}`", name);
+        }
+    }
+}
+
+fn attr_name_string(named_attr: &ast::NamedAttr) -> String {
+    named_attr
+        .name
+        .clone()
+        .into_iter()
+        .map(|token| token.to_string())
+        .collect()
+}
+
+/// Reports any literal ID that is used more than once in a template.
+///
+/// `seen` holds the IDs found so far. IDs in different branches of an `@if`
+/// or `@match` don't conflict with each other, as only one of them is
+/// rendered; nor do IDs in `@debug_only` and `@release_only`, which are never
+/// in the same build. `debug` is `Some` inside one of those blocks, and says
+/// which builds it's rendered in. Spliced IDs can't be checked, so they are
+/// skipped.
+fn check_duplicate_ids(markups: &[ast::Markup], seen: &mut Vec<SeenId>, debug: Option<bool>) {
+    for markup in markups {
+        match markup {
+            ast::Markup::Block(block) => check_duplicate_ids(&block.markups, seen, debug),
+            ast::Markup::Element { attrs, body, .. } => {
+                for attr in attrs {
+                    if let Some(id) = literal_id(attr) {
+                        insert_id(id, attr.span(), seen, debug);
+                    }
+                }
+                if let ast::ElementBody::Block { block } = body {
+                    check_duplicate_ids(&block.markups, seen, debug);
+                }
+            }
+            ast::Markup::Special { segments } => match build_only(segments) {
+                Some(build) => check_duplicate_ids(&segments[0].body.markups, seen, Some(build)),
+                None => {
+                    let branches = segments.iter().map(|segment| &segment.body);
+                    check_duplicate_ids_in_branches(branches, seen, debug);
+                }
+            },
+            ast::Markup::Slot { body, .. } | ast::Markup::RawBlock { body, .. } => {
+                check_duplicate_ids(&body.markups, seen, debug)
+            }
+            // A definition can be used any number of times, anywhere, so
+            // its IDs are only checked against each other
+            ast::Markup::Define { body, .. } => {
+                check_duplicate_ids(&body.markups, &mut Vec::new(), debug)
+            }
+            ast::Markup::Match { arms, .. } => {
+                let branches = arms.iter().map(|arm| &arm.body);
+                check_duplicate_ids_in_branches(branches, seen, debug);
+            }
+            // The rest of the block is skipped when the `else` is taken, so
+            // IDs in the `else` don't conflict with anything after it
+            ast::Markup::Let {
+                else_block: Some(else_block),
+                ..
+            } => check_duplicate_ids(&else_block.markups, &mut seen.clone(), debug),
+            ast::Markup::ParseError { .. }
+            | ast::Markup::Literal { .. }
+            | ast::Markup::Symbol { .. }
+            | ast::Markup::Splice { .. }
+            | ast::Markup::Use { .. }
+            | ast::Markup::Let { .. }
+            | ast::Markup::Dependency { .. } => {}
+        }
+    }
+}
+
+/// An ID found by [`check_duplicate_ids`], with where it's first used and
+/// which builds it's rendered in.
+type SeenId = (String, SpanRange, Option<bool>);
+
+/// If `segments` is a `@debug_only` or `@release_only` block, returns whether
+/// it's rendered in debug builds.
+fn build_only(segments: &[ast::Special]) -> Option<bool> {
+    let [segment] = segments else {
+        return None;
+    };
+    let head = segment.head.to_string();
+    if head == quote!(#[cfg(debug_assertions)]).to_string() {
+        Some(true)
+    } else if head == quote!(#[cfg(not(debug_assertions))]).to_string() {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn check_duplicate_ids_in_branches<'a>(
+    branches: impl Iterator<Item = &'a ast::Block>,
+    seen: &mut Vec<SeenId>,
+    debug: Option<bool>,
+) {
+    let mut found = Vec::new();
+    for branch in branches {
+        let mut branch_seen = seen.clone();
+        check_duplicate_ids(&branch.markups, &mut branch_seen, debug);
+        found.extend(branch_seen.drain(seen.len()..));
+    }
+    for (id, span, build) in found {
+        if !seen
+            .iter()
+            .any(|(other, _, other_build)| *other == id && *other_build == build)
+        {
+            seen.push((id, span, build));
+        }
+    }
+}
+
+fn insert_id(id: String, span: SpanRange, seen: &mut Vec<SeenId>, debug: Option<bool>) {
+    let conflict = seen.iter().find(|(other, _, other_debug)| {
+        *other == id && (debug.is_none() || other_debug.is_none() || debug == *other_debug)
+    });
+    if let Some((_, first_span, _)) = conflict {
+        emit_error!(
+            span,
+            "duplicate id `{}`", id;
+            note = first_span.first => "`{}` is first used here", id;
+        );
+    } else {
+        seen.push((id, span, debug));
+    }
+}
+
+/// Reports each splice and control structure in `markups`, none of which
+/// are allowed in `html_static!`.
+fn check_static(markups: &[ast::Markup]) {
+    for markup in markups {
+        match markup {
+            ast::Markup::ParseError { .. }
+            | ast::Markup::Literal { .. }
+            | ast::Markup::Symbol { .. }
+            | ast::Markup::Dependency { .. } => {}
+            ast::Markup::Block(block) | ast::Markup::RawBlock { body: block, .. } => {
+                check_static(&block.markups);
+            }
+            ast::Markup::Element { attrs, body, .. } => {
+                for attr in attrs {
+                    check_static_attr(attr);
+                }
+                if let ast::ElementBody::Block { block } = body {
+                    check_static(&block.markups);
+                }
+            }
+            ast::Markup::Splice { outer_span, .. } => emit_error!(
+                outer_span,
+                "`html_static!` can't contain splices";
+                help = "use `html!` for templates with dynamic content"
+            ),
+            markup => emit_error!(
+                markup.span(),
+                "`html_static!` can't contain control structures";
+                help = "use `html!` for templates with dynamic content"
+            ),
+        }
+    }
+}
+
+fn check_static_attr(attr: &ast::Attr) {
+    let dynamic_span = match attr {
+        ast::Attr::Class { name, toggler, .. } => {
+            check_static(slice::from_ref(name));
+            toggler.as_ref().map(|toggler| toggler.cond_span)
+        }
+        ast::Attr::Id { name, .. } => {
+            check_static(slice::from_ref(name));
+            None
+        }
+        ast::Attr::Named { named_attr } => match &named_attr.attr_type {
+            ast::AttrType::Normal { value } => {
+                check_static(slice::from_ref(value));
+                None
+            }
+            ast::AttrType::Toggled { toggler, .. }
+            | ast::AttrType::Optional { toggler, .. }
+            | ast::AttrType::Empty {
+                toggler: Some(toggler),
+            } => Some(toggler.cond_span),
+            ast::AttrType::Empty { toggler: None } => None,
+        },
+        ast::Attr::Raw { .. } | ast::Attr::Toggle { .. } => Some(attr.span()),
+    };
+    if let Some(span) = dynamic_span {
+        emit_error!(
+            span,
+            "`html_static!` can't contain toggles or attribute splices";
+            help = "use `html!` for templates with dynamic content"
+        );
+    }
+}
+
+/// Returns the value of an `id` attribute, if it's written as a literal.
+fn literal_id(attr: &ast::Attr) -> Option<String> {
+    let value = match attr {
+        ast::Attr::Id { name, .. } => name,
+        ast::Attr::Named {
+            named_attr:
+                ast::NamedAttr {
+                    name,
+                    attr_type: ast::AttrType::Normal { value },
+                },
+        } if ast::name_to_string(name.clone()) == "id" => value,
+        _ => return None,
+    };
+    match value {
+        ast::Markup::Symbol { symbol } => Some(ast::name_to_string(symbol.clone())),
+        ast::Markup::Literal { content, .. } => Some(content.clone()),
+        _ => None,
+    }
+}